@@ -0,0 +1,240 @@
+//! Mock of `pallet_assets` behind `AssetsFunc`.
+//!
+//! Balances, approvals and metadata are modelled. Freezing is not, and deposits
+//! are neither reserved nor checked.
+
+use std::collections::BTreeMap;
+
+use assets_chain_extension_types::{AssetId, AssetsFunc, AssetsOrigin, StatusCode};
+use ink_env::AccountId;
+use scale::Encode;
+
+use crate::{decode_input, Origins};
+
+pub type Balance = u128;
+
+/// Details and metadata of an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    pub owner: AccountId,
+    /// May burn
+    pub admin: AccountId,
+    /// May mint
+    pub issuer: AccountId,
+    pub min_balance: Balance,
+    pub supply: Balance,
+    pub name: Vec<u8>,
+    pub symbol: Vec<u8>,
+    pub decimals: u8,
+}
+
+impl Asset {
+    /// An asset without metadata, fully controlled by `owner`.
+    pub fn new(owner: AccountId, min_balance: Balance) -> Self {
+        Self {
+            owner,
+            admin: owner,
+            issuer: owner,
+            min_balance,
+            supply: 0,
+            name: Vec::new(),
+            symbol: Vec::new(),
+            decimals: 0,
+        }
+    }
+}
+
+/// State of the mocked assets pallet.
+#[derive(Debug, Clone, Default)]
+pub struct Assets {
+    pub assets: BTreeMap<AssetId, Asset>,
+    /// Balances by asset and account, an account below the minimum balance is removed
+    pub balances: BTreeMap<(AssetId, AccountId), Balance>,
+    /// Approved amounts by asset, owner and delegate
+    pub approvals: BTreeMap<(AssetId, AccountId, AccountId), Balance>,
+}
+
+impl Assets {
+    pub fn balance(&self, id: AssetId, who: AccountId) -> Balance {
+        self.balances.get(&(id, who)).copied().unwrap_or_default()
+    }
+
+    pub fn allowance(&self, id: AssetId, owner: AccountId, delegate: AccountId) -> Balance {
+        self.approvals.get(&(id, owner, delegate)).copied().unwrap_or_default()
+    }
+
+    fn asset(&self, id: AssetId) -> Result<&Asset, StatusCode> {
+        self.assets.get(&id).ok_or(StatusCode::AssetUnknown)
+    }
+
+    fn set_balance(&mut self, id: AssetId, who: AccountId, balance: Balance) {
+        if balance == 0 {
+            self.balances.remove(&(id, who));
+        } else {
+            self.balances.insert((id, who), balance);
+        }
+    }
+
+    /// Moves `amount` like `pallet_assets`, which moves the whole balance of `source`
+    /// rather than leave it below the minimum balance.
+    fn transfer(
+        &mut self,
+        id: AssetId,
+        source: AccountId,
+        dest: AccountId,
+        amount: Balance,
+    ) -> Result<(), StatusCode> {
+        let min_balance = self.asset(id)?.min_balance;
+        let source_balance = self.balance(id, source);
+        if amount > source_balance {
+            return Err(StatusCode::BalanceLow)
+        }
+        let amount = if source_balance - amount < min_balance {
+            source_balance
+        } else {
+            amount
+        };
+        if source == dest || amount == 0 {
+            return Ok(())
+        }
+        let dest_balance = self.balance(id, dest) + amount;
+        if dest_balance < min_balance {
+            return Err(StatusCode::BelowMinimum)
+        }
+        self.set_balance(id, source, source_balance - amount);
+        self.set_balance(id, dest, dest_balance);
+        Ok(())
+    }
+}
+
+pub(crate) fn dispatch(
+    state: &mut Assets,
+    func: AssetsFunc,
+    input: &[u8],
+    origins: Origins,
+) -> Result<Vec<u8>, StatusCode> {
+    let signer = |origin: AssetsOrigin| match origin {
+        AssetsOrigin::Caller => origins.caller,
+        AssetsOrigin::Address => origins.address,
+    };
+    match func {
+        AssetsFunc::Create => {
+            let (origin, id, admin, min_balance): (AssetsOrigin, AssetId, AccountId, Balance) =
+                decode_input(func, input);
+            if state.assets.contains_key(&id) {
+                return Err(StatusCode::AssetExists)
+            }
+            if min_balance == 0 {
+                return Err(StatusCode::BelowMinimum)
+            }
+            let asset = Asset {
+                admin,
+                issuer: admin,
+                ..Asset::new(signer(origin), min_balance)
+            };
+            state.assets.insert(id, asset);
+            Ok(Vec::new())
+        }
+
+        AssetsFunc::Mint => {
+            let (origin, id, beneficiary, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                decode_input(func, input);
+            let asset = state.asset(id)?;
+            if asset.issuer != signer(origin) {
+                return Err(StatusCode::NoPermission)
+            }
+            let balance = state.balance(id, beneficiary) + amount;
+            if balance < asset.min_balance {
+                return Err(StatusCode::BelowMinimum)
+            }
+            state.set_balance(id, beneficiary, balance);
+            state.assets.get_mut(&id).expect("checked above").supply += amount;
+            Ok(Vec::new())
+        }
+
+        AssetsFunc::Burn => {
+            let (origin, id, who, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                decode_input(func, input);
+            let asset = state.asset(id)?;
+            if asset.admin != signer(origin) {
+                return Err(StatusCode::NoPermission)
+            }
+            // Burns up to `amount`, and the dust below the minimum balance with it.
+            let balance = state.balance(id, who);
+            let mut burned = amount.min(balance);
+            if balance - burned < asset.min_balance {
+                burned = balance;
+            }
+            state.set_balance(id, who, balance - burned);
+            state.assets.get_mut(&id).expect("checked above").supply -= burned;
+            Ok(Vec::new())
+        }
+
+        AssetsFunc::Transfer => {
+            let (origin, id, target, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                decode_input(func, input);
+            state.transfer(id, signer(origin), target, amount)?;
+            Ok(Vec::new())
+        }
+
+        AssetsFunc::Approve => {
+            let (origin, id, delegate, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                decode_input(func, input);
+            state.asset(id)?;
+            let key = (id, signer(origin), delegate);
+            if amount == 0 {
+                state.approvals.remove(&key);
+            } else {
+                state.approvals.insert(key, amount);
+            }
+            Ok(Vec::new())
+        }
+
+        AssetsFunc::TransferApproved => {
+            let (origin, id, owner, destination, amount): (
+                AssetsOrigin,
+                AssetId,
+                AccountId,
+                AccountId,
+                Balance,
+            ) = decode_input(func, input);
+            let delegate = signer(origin);
+            let approved = state.allowance(id, owner, delegate);
+            if approved < amount {
+                return Err(StatusCode::Unapproved)
+            }
+            state.transfer(id, owner, destination, amount)?;
+            if approved == amount {
+                state.approvals.remove(&(id, owner, delegate));
+            } else {
+                state.approvals.insert((id, owner, delegate), approved - amount);
+            }
+            Ok(Vec::new())
+        }
+
+        AssetsFunc::BalanceOf => {
+            let (id, who): (AssetId, AccountId) = decode_input(func, input);
+            Ok(state.balance(id, who).encode())
+        }
+
+        AssetsFunc::TotalSupply => {
+            let id: AssetId = decode_input(func, input);
+            Ok(state.assets.get(&id).map_or(0, |asset| asset.supply).encode())
+        }
+
+        AssetsFunc::Metadata => {
+            let id: AssetId = decode_input(func, input);
+            let metadata = state
+                .assets
+                .get(&id)
+                .map(|asset| (asset.name.clone(), asset.symbol.clone(), asset.decimals))
+                .unwrap_or_default();
+            Ok(metadata.encode())
+        }
+
+        AssetsFunc::Allowance => {
+            let (id, owner, delegate): (AssetId, AccountId, AccountId) = decode_input(func, input);
+            Ok(state.allowance(id, owner, delegate).encode())
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Mock of `pallet_dapps_staking` behind `DappsStakingFunc`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use dapps_staking_chain_extension_types::{DappsStakingFunc, StakingOrigin, StatusCode};
+use ink_env::AccountId;
+use scale::{Decode, Encode};
+
+use crate::{decode_input, Origins};
+
+pub type Balance = u128;
+
+/// Encoded the same way as `ds::SmartContract<AccountId>`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
+pub enum SmartContract {
+    Evm([u8; 20]),
+    Wasm(AccountId),
+}
+
+/// Encoded the same way as `ds::EraInfo`, with the `RewardInfo` fields inlined.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct EraInfo {
+    #[codec(compact)]
+    pub stakers_reward: Balance,
+    #[codec(compact)]
+    pub dapps_reward: Balance,
+    #[codec(compact)]
+    pub staked: Balance,
+    #[codec(compact)]
+    pub locked: Balance,
+}
+
+/// Encoded the same way as `ds::ContractStakeInfo`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct ContractStakeInfo {
+    #[codec(compact)]
+    pub total: Balance,
+    #[codec(compact)]
+    pub number_of_stakers: u32,
+    pub contract_reward_claimed: bool,
+}
+
+/// Encoded the same way as `ds::UnlockingChunk`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct UnlockingChunk {
+    #[codec(compact)]
+    pub amount: Balance,
+    #[codec(compact)]
+    pub unlock_era: u32,
+}
+
+/// State of the mocked dapps staking.
+///
+/// Stakes are not tracked per era; `ContractStake` answers with the current
+/// stakes for any era of a registered contract.
+#[derive(Debug, Clone, Default)]
+pub struct DappsStaking {
+    pub current_era: u32,
+    /// Answers of `GeneralEraInfo`, `EraNotFound` for a missing era
+    pub eras: BTreeMap<u32, EraInfo>,
+    pub registered: BTreeSet<SmartContract>,
+    /// Staked amount by staker and contract
+    pub stakes: BTreeMap<(AccountId, SmartContract), Balance>,
+    pub unbonding: BTreeMap<AccountId, Vec<UnlockingChunk>>,
+    /// Eras an unbonded chunk waits before it can be withdrawn
+    pub unbonding_period: u32,
+    /// Smallest stake a staker may hold on a contract
+    pub minimum_stake: Balance,
+    /// Paid out by every `ClaimStaker`
+    pub staker_reward: Balance,
+    /// Paid out by every `ClaimDapp`
+    pub dapp_reward: Balance,
+}
+
+impl DappsStaking {
+    pub fn staked(&self, staker: AccountId, contract_id: SmartContract) -> Balance {
+        self.stakes.get(&(staker, contract_id)).copied().unwrap_or_default()
+    }
+
+    fn ensure_registered(&self, contract_id: SmartContract) -> Result<(), StatusCode> {
+        if !self.registered.contains(&contract_id) {
+            return Err(StatusCode::NotRegistered)
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn dispatch(
+    state: &mut DappsStaking,
+    func: DappsStakingFunc,
+    input: &[u8],
+    origins: Origins,
+) -> Result<Vec<u8>, StatusCode> {
+    let signer = |origin: StakingOrigin| match origin {
+        StakingOrigin::Caller => origins.caller,
+        StakingOrigin::Address => origins.address,
+    };
+    match func {
+        DappsStakingFunc::CurrentEra => Ok(state.current_era.encode()),
+
+        DappsStakingFunc::GeneralEraInfo => {
+            let era: u32 = decode_input(func, input);
+            state
+                .eras
+                .get(&era)
+                .map(Encode::encode)
+                .ok_or(StatusCode::EraNotFound)
+        }
+
+        DappsStakingFunc::BondAndStake => {
+            let (staker, contract_id, value): (StakingOrigin, SmartContract, Balance) =
+                decode_input(func, input);
+            state.ensure_registered(contract_id)?;
+            let staked = state.staked(signer(staker), contract_id) + value;
+            if value == 0 || staked < state.minimum_stake {
+                return Err(StatusCode::InsufficientValue)
+            }
+            state.stakes.insert((signer(staker), contract_id), staked);
+            Ok(Vec::new())
+        }
+
+        DappsStakingFunc::UnbondAndUnstake => {
+            let (staker, contract_id, value): (StakingOrigin, SmartContract, Balance) =
+                decode_input(func, input);
+            state.ensure_registered(contract_id)?;
+            let staker = signer(staker);
+            let staked = state.staked(staker, contract_id);
+            if staked == 0 {
+                return Err(StatusCode::NotStaked)
+            }
+            if value == 0 || value > staked {
+                return Err(StatusCode::InsufficientValue)
+            }
+            state.stakes.insert((staker, contract_id), staked - value);
+            let unlock_era = state.current_era + state.unbonding_period;
+            state
+                .unbonding
+                .entry(staker)
+                .or_default()
+                .push(UnlockingChunk { amount: value, unlock_era });
+            Ok(Vec::new())
+        }
+
+        DappsStakingFunc::WithdrawUnbonded => {
+            let staker: StakingOrigin = decode_input(func, input);
+            let current_era = state.current_era;
+            let chunks = state.unbonding.entry(signer(staker)).or_default();
+            let pending = chunks.len();
+            chunks.retain(|chunk| chunk.unlock_era > current_era);
+            if chunks.len() == pending {
+                return Err(StatusCode::NothingToWithdraw)
+            }
+            Ok(Vec::new())
+        }
+
+        DappsStakingFunc::ClaimStaker => {
+            let (claimer, contract_id): (StakingOrigin, SmartContract) = decode_input(func, input);
+            state.ensure_registered(contract_id)?;
+            if state.staked(signer(claimer), contract_id) == 0 {
+                return Err(StatusCode::NotStaked)
+            }
+            Ok(state.staker_reward.encode())
+        }
+
+        DappsStakingFunc::ClaimDapp => {
+            let (_, contract_id, era): (StakingOrigin, SmartContract, u32) =
+                decode_input(func, input);
+            state.ensure_registered(contract_id)?;
+            if era >= state.current_era {
+                return Err(StatusCode::EraNotFound)
+            }
+            Ok(state.dapp_reward.encode())
+        }
+
+        DappsStakingFunc::StakedAmount => {
+            let (account, contract_id): (AccountId, SmartContract) = decode_input(func, input);
+            Ok(state.staked(account, contract_id).encode())
+        }
+
+        DappsStakingFunc::ContractStake => {
+            let (contract_id, _era): (SmartContract, u32) = decode_input(func, input);
+            let contract_stake = state.registered.contains(&contract_id).then(|| {
+                let stakes = state
+                    .stakes
+                    .iter()
+                    .filter(|((_, staked_on), value)| *staked_on == contract_id && **value > 0);
+                ContractStakeInfo {
+                    total: stakes.clone().map(|(_, value)| value).sum(),
+                    number_of_stakers: stakes.count() as u32,
+                    contract_reward_claimed: false,
+                }
+            });
+            Ok(contract_stake.encode())
+        }
+
+        DappsStakingFunc::UnbondingInfo => {
+            let account: AccountId = decode_input(func, input);
+            Ok(state.unbonding.get(&account).cloned().unwrap_or_default().encode())
+        }
+
+        DappsStakingFunc::Register => {
+            let (_, contract_id): (StakingOrigin, SmartContract) = decode_input(func, input);
+            if !state.registered.insert(contract_id) {
+                return Err(StatusCode::AlreadyRegistered)
+            }
+            Ok(Vec::new())
+        }
+    }
+}
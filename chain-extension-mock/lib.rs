@@ -0,0 +1,202 @@
+//! An in-memory stand-in for the runtime `LocalChainExtension`, for `#[ink::test]`s.
+//!
+//! `MockRuntime::new` registers one mock per function ID of the dapps staking, RMRK
+//! and assets extensions. The mocks decode the SCALE input of the contract, act on
+//! shared state and answer with the output and status code the runtime would. Tests
+//! seed or inspect that state through `MockRuntime::dapps_staking`, `MockRuntime::rmrk`
+//! and `MockRuntime::assets`, and script failures with `MockRuntime::fail_next`.
+//!
+//! ```ignore
+//! #[ink::test]
+//! fn register_works() {
+//!     let runtime = MockRuntime::new();
+//!     let mut ds = DappsStakingExtension::new();
+//!     runtime.fail_next(DappsStakingFunc::Register, StatusCode::Failed);
+//!     assert_eq!(ds.register(), Err(DSError::ErrorCode(DSErrorCode::Failed)));
+//!     assert_eq!(ds.register(), Ok(()));
+//! }
+//! ```
+//!
+//! A mock registered by the test after `MockRuntime::new` replaces the one for its
+//! function ID, e.g. for the RMRK functions not modelled here.
+//!
+//! Governance is not modelled, every contract may sign with its caller as if it were
+//! in the `CallerSigningContracts` of the runtime.
+
+pub mod assets;
+pub mod dapps_staking;
+pub mod rmrk;
+
+use std::{
+    cell::{RefCell, RefMut},
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+};
+
+use assets_chain_extension_types::AssetsFunc;
+use dapps_staking_chain_extension_types::DappsStakingFunc;
+use ink_env::{AccountId, DefaultEnvironment};
+use rmrk_chain_extension_types::RmrkFunc;
+use scale::Decode;
+
+pub use assets::Assets;
+pub use dapps_staking::DappsStaking;
+pub use rmrk::Rmrk;
+
+/// Accounts a call can be signed with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Origins {
+    /// The account calling the contract
+    pub caller: AccountId,
+    /// The contract itself
+    pub address: AccountId,
+}
+
+struct State {
+    origins: Origins,
+    /// Status codes to answer with instead of calling the mock, by function ID
+    scripted: BTreeMap<u32, VecDeque<u32>>,
+    dapps_staking: DappsStaking,
+    rmrk: Rmrk,
+    assets: Assets,
+}
+
+/// Handle on the state shared by the registered mocks.
+pub struct MockRuntime {
+    state: Rc<RefCell<State>>,
+}
+
+impl MockRuntime {
+    /// Registers the mocks, acting for the current caller and callee of the test.
+    pub fn new() -> Self {
+        let origins = Origins {
+            caller: ink_env::caller::<DefaultEnvironment>(),
+            address: ink_env::test::callee::<DefaultEnvironment>(),
+        };
+        let state = Rc::new(RefCell::new(State {
+            origins,
+            scripted: BTreeMap::new(),
+            dapps_staking: DappsStaking::default(),
+            rmrk: Rmrk::default(),
+            assets: Assets::default(),
+        }));
+        for func in DappsStakingFunc::ALL {
+            ink_env::test::register_chain_extension(MockedFunc {
+                func: Func::DappsStaking(*func),
+                state: state.clone(),
+            });
+        }
+        for func in RmrkFunc::ALL {
+            ink_env::test::register_chain_extension(MockedFunc {
+                func: Func::Rmrk(*func),
+                state: state.clone(),
+            });
+        }
+        for func in AssetsFunc::ALL {
+            ink_env::test::register_chain_extension(MockedFunc {
+                func: Func::Assets(*func),
+                state: state.clone(),
+            });
+        }
+        Self { state }
+    }
+
+    /// Sets the caller of the contract, for the test and for the mocks.
+    ///
+    /// The mocks cannot ask the off-chain environment while it is calling them,
+    /// so use this instead of `ink_env::test::set_caller`.
+    pub fn set_caller(&self, caller: AccountId) {
+        ink_env::test::set_caller::<DefaultEnvironment>(caller);
+        self.state.borrow_mut().origins.caller = caller;
+    }
+
+    pub fn origins(&self) -> Origins {
+        self.state.borrow().origins
+    }
+
+    /// Answers the next call of `func_id` with `status` and an empty output,
+    /// without touching the state. Queues up when called repeatedly.
+    pub fn fail_next(&self, func_id: impl Into<u32>, status: impl Into<u32>) {
+        self.state
+            .borrow_mut()
+            .scripted
+            .entry(func_id.into())
+            .or_default()
+            .push_back(status.into());
+    }
+
+    pub fn dapps_staking(&self) -> RefMut<'_, DappsStaking> {
+        RefMut::map(self.state.borrow_mut(), |state| &mut state.dapps_staking)
+    }
+
+    pub fn rmrk(&self) -> RefMut<'_, Rmrk> {
+        RefMut::map(self.state.borrow_mut(), |state| &mut state.rmrk)
+    }
+
+    pub fn assets(&self) -> RefMut<'_, Assets> {
+        RefMut::map(self.state.borrow_mut(), |state| &mut state.assets)
+    }
+}
+
+impl Default for MockRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Func {
+    DappsStaking(DappsStakingFunc),
+    Rmrk(RmrkFunc),
+    Assets(AssetsFunc),
+}
+
+/// The mock of one function ID.
+struct MockedFunc {
+    func: Func,
+    state: Rc<RefCell<State>>,
+}
+
+impl ink_env::test::ChainExtension for MockedFunc {
+    fn func_id(&self) -> u32 {
+        match self.func {
+            Func::DappsStaking(func) => func.into(),
+            Func::Rmrk(func) => func.into(),
+            Func::Assets(func) => func.into(),
+        }
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        // The off-chain engine hands over the encoded input as a `Vec<u8>`.
+        let input: Vec<u8> = Decode::decode(&mut &input[..]).expect("input is a `Vec<u8>`");
+        let func_id = self.func_id();
+        let state = &mut *self.state.borrow_mut();
+        if let Some(status) = state.scripted.get_mut(&func_id).and_then(VecDeque::pop_front) {
+            return status
+        }
+        let outcome = match self.func {
+            Func::DappsStaking(func) => {
+                dapps_staking::dispatch(&mut state.dapps_staking, func, &input, state.origins)
+                    .map_err(u32::from)
+            }
+            Func::Rmrk(func) => {
+                rmrk::dispatch(&mut state.rmrk, func, &input, state.origins).map_err(u32::from)
+            }
+            Func::Assets(func) => {
+                assets::dispatch(&mut state.assets, func, &input, state.origins).map_err(u32::from)
+            }
+        };
+        match outcome {
+            Ok(encoded) => {
+                output.extend(encoded);
+                0
+            }
+            Err(status) => status,
+        }
+    }
+}
+
+/// Decodes the arguments of `func`, an undecodable input traps like in the runtime.
+fn decode_input<T: Decode>(func: impl core::fmt::Debug, mut input: &[u8]) -> T {
+    T::decode(&mut input).unwrap_or_else(|_| panic!("{:?} failed to decode its input", func))
+}
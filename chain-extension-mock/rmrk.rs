@@ -0,0 +1,288 @@
+//! Mock of `pallet_rmrk_core` behind `RmrkFunc`.
+//!
+//! Collections, NFTs, nesting and properties are modelled. Accepting and rejecting
+//! pending NFTs, resources, bases and equipping are not: their functions fail with
+//! `StatusCode::Failed` unless the test registers its own mock.
+
+use std::collections::BTreeMap;
+
+use ink_env::AccountId;
+use rmrk_chain_extension_types::{RmrkFunc, StatusCode, RMRK_BALANCE_LIMIT, RMRK_PAGE_LIMIT};
+use scale::{Decode, Encode};
+
+use crate::{decode_input, Origins};
+
+/// Encoded the same way as `rmrk_chain_test::AccountIdOrCollectionNftTuple`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum Owner {
+    AccountId(AccountId),
+    CollectionAndNftTuple(u32, u32),
+}
+
+/// Encoded the same way as `rmrk_chain_test::CollectionInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Collection {
+    pub issuer: AccountId,
+    pub metadata: Vec<u8>,
+    pub max: Option<u32>,
+    pub symbol: Vec<u8>,
+    pub nfts_count: u32,
+}
+
+/// Encoded the same way as `rmrk_chain_test::NftInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Nft {
+    pub owner: Owner,
+    /// Recipient and parts per million
+    pub royalty: Option<(AccountId, u32)>,
+    pub metadata: Vec<u8>,
+    pub equipped: bool,
+    pub pending: bool,
+    pub transferable: bool,
+}
+
+/// State of the mocked RMRK pallet.
+#[derive(Debug, Clone, Default)]
+pub struct Rmrk {
+    /// Collections by ID, there is no way to destroy one
+    pub collections: Vec<Collection>,
+    pub next_nft_ids: BTreeMap<u32, u32>,
+    pub nfts: BTreeMap<(u32, u32), Nft>,
+    /// Values by collection ID, NFT ID (`None` for the collection itself) and key
+    pub properties: BTreeMap<(u32, Option<u32>, Vec<u8>), Vec<u8>>,
+}
+
+impl Rmrk {
+    /// The account at the root of the nesting tree of an NFT.
+    pub fn root_owner(&self, collection_id: u32, nft_id: u32) -> Option<AccountId> {
+        match self.nfts.get(&(collection_id, nft_id))?.owner {
+            Owner::AccountId(owner) => Some(owner),
+            Owner::CollectionAndNftTuple(collection_id, nft_id) => {
+                self.root_owner(collection_id, nft_id)
+            }
+        }
+    }
+
+    pub fn children(&self, collection_id: u32, nft_id: u32) -> Vec<(u32, u32)> {
+        let parent = Owner::CollectionAndNftTuple(collection_id, nft_id);
+        self.nfts
+            .iter()
+            .filter(|(_, nft)| nft.owner == parent)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Whether `nft` is `ancestor` or nested somewhere below it.
+    fn is_descendant(&self, nft: (u32, u32), ancestor: (u32, u32)) -> bool {
+        nft == ancestor
+            || matches!(
+                self.nfts.get(&nft).map(|nft| nft.owner),
+                Some(Owner::CollectionAndNftTuple(c, n)) if self.is_descendant((c, n), ancestor)
+            )
+    }
+
+    fn burn(&mut self, collection_id: u32, nft_id: u32) {
+        for (c, n) in self.children(collection_id, nft_id) {
+            self.burn(c, n);
+        }
+        self.nfts.remove(&(collection_id, nft_id));
+        self.collections[collection_id as usize].nfts_count -= 1;
+    }
+
+    fn ensure_root_owner(
+        &self,
+        collection_id: u32,
+        nft_id: u32,
+        signer: AccountId,
+    ) -> Result<(), StatusCode> {
+        match self.root_owner(collection_id, nft_id) {
+            None => Err(StatusCode::NftUnknown),
+            Some(owner) if owner != signer => Err(StatusCode::NoPermission),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+pub(crate) fn dispatch(
+    state: &mut Rmrk,
+    func: RmrkFunc,
+    input: &[u8],
+    origins: Origins,
+) -> Result<Vec<u8>, StatusCode> {
+    // The contract itself signs, unless the function explicitly acts for its caller.
+    let signer = match func {
+        RmrkFunc::SendFromCaller => origins.caller,
+        _ => origins.address,
+    };
+    match func {
+        RmrkFunc::NextNftId => {
+            let collection_id: u32 = decode_input(func, input);
+            Ok(state.next_nft_ids.get(&collection_id).copied().unwrap_or_default().encode())
+        }
+
+        RmrkFunc::CollectionIndex => Ok((state.collections.len() as u32).encode()),
+
+        RmrkFunc::CollectionInfo => {
+            let collection_id: u32 = decode_input(func, input);
+            Ok(state.collections.get(collection_id as usize).encode())
+        }
+
+        RmrkFunc::NftInfo => {
+            let (collection_id, nft_id): (u32, u32) = decode_input(func, input);
+            Ok(state.nfts.get(&(collection_id, nft_id)).encode())
+        }
+
+        // Pages follow the order of the mocked maps, the runtime follows the storage order.
+        RmrkFunc::Children => {
+            let (collection_id, nft_id, start, limit): (u32, u32, Option<(u32, u32)>, u32) =
+                decode_input(func, input);
+            let children: Vec<(u32, u32)> = state
+                .children(collection_id, nft_id)
+                .into_iter()
+                .filter(|child| start.is_none_or(|start| *child > start))
+                .take(limit.min(RMRK_PAGE_LIMIT) as usize)
+                .collect();
+            Ok(children.encode())
+        }
+
+        RmrkFunc::Properties => {
+            let (collection_id, nft_id, start, limit): (u32, Option<u32>, Option<Vec<u8>>, u32) =
+                decode_input(func, input);
+            let properties: Vec<(Vec<u8>, Vec<u8>)> = state
+                .properties
+                .iter()
+                .filter(|((c, n, _), _)| (*c, *n) == (collection_id, nft_id))
+                .filter(|((_, _, key), _)| start.as_ref().is_none_or(|start| key > start))
+                .take(limit.min(RMRK_PAGE_LIMIT) as usize)
+                .map(|((_, _, key), value)| (key.clone(), value.clone()))
+                .collect();
+            Ok(properties.encode())
+        }
+
+        RmrkFunc::BalanceOf => {
+            let (owner, collection_id, start): (AccountId, u32, Option<u32>) =
+                decode_input(func, input);
+            let page: Vec<u32> = state
+                .nfts
+                .iter()
+                .filter(|((c, _), nft)| *c == collection_id && nft.owner == Owner::AccountId(owner))
+                .map(|((_, nft_id), _)| *nft_id)
+                .filter(|nft_id| start.is_none_or(|start| *nft_id > start))
+                .take(RMRK_BALANCE_LIMIT as usize)
+                .collect();
+            let full = page.len() == RMRK_BALANCE_LIMIT as usize;
+            let last = if full { page.last().copied() } else { None };
+            Ok((page.len() as u32, last).encode())
+        }
+
+        RmrkFunc::MintNft => {
+            // The resources to add on mint are not modelled.
+            let (owner, collection_id, royalty_recipient, royalty, metadata, transferable): (
+                AccountId,
+                u32,
+                Option<AccountId>,
+                Option<u32>,
+                Vec<u8>,
+                bool,
+            ) = decode_input(func, input);
+            let collection = state
+                .collections
+                .get_mut(collection_id as usize)
+                .ok_or(StatusCode::CollectionUnknown)?;
+            if collection.issuer != signer {
+                return Err(StatusCode::NoPermission)
+            }
+            if royalty.is_some_and(|royalty| royalty > 1_000_000) {
+                return Err(StatusCode::RoyaltyOutOfRange)
+            }
+            if collection.max.is_some_and(|max| collection.nfts_count >= max) {
+                return Err(StatusCode::CollectionFullOrLocked)
+            }
+            collection.nfts_count += 1;
+            let next_nft_id = state.next_nft_ids.entry(collection_id).or_default();
+            let nft_id = *next_nft_id;
+            *next_nft_id += 1;
+            let nft = Nft {
+                owner: Owner::AccountId(owner),
+                royalty: royalty.map(|amount| (royalty_recipient.unwrap_or(owner), amount)),
+                metadata,
+                equipped: false,
+                pending: false,
+                transferable,
+            };
+            state.nfts.insert((collection_id, nft_id), nft);
+            Ok((collection_id, nft_id).encode())
+        }
+
+        RmrkFunc::CreateCollection => {
+            let (metadata, max, symbol): (Vec<u8>, Option<u32>, Vec<u8>) = decode_input(func, input);
+            let collection_id = state.collections.len() as u32;
+            state.collections.push(Collection {
+                issuer: signer,
+                metadata,
+                max,
+                symbol,
+                nfts_count: 0,
+            });
+            Ok(collection_id.encode())
+        }
+
+        RmrkFunc::BurnNft => {
+            let (collection_id, nft_id): (u32, u32) = decode_input(func, input);
+            state.ensure_root_owner(collection_id, nft_id, signer)?;
+            state.burn(collection_id, nft_id);
+            Ok(Vec::new())
+        }
+
+        RmrkFunc::Send | RmrkFunc::SendFromCaller => {
+            let (collection_id, nft_id, new_owner): (u32, u32, Owner) = decode_input(func, input);
+            state.ensure_root_owner(collection_id, nft_id, signer)?;
+            if !state.nfts[&(collection_id, nft_id)].transferable {
+                return Err(StatusCode::NonTransferable)
+            }
+            let pending = match new_owner {
+                Owner::AccountId(_) => false,
+                Owner::CollectionAndNftTuple(c, n) => {
+                    if state.is_descendant((c, n), (collection_id, nft_id)) {
+                        return Err(StatusCode::InvalidDestination)
+                    }
+                    let parent_owner = state.root_owner(c, n).ok_or(StatusCode::NftUnknown)?;
+                    parent_owner != signer
+                }
+            };
+            let nft = state.nfts.get_mut(&(collection_id, nft_id)).expect("checked above");
+            nft.owner = new_owner;
+            nft.pending = pending;
+            Ok(Vec::new())
+        }
+
+        RmrkFunc::ChangeCollectionIssuer => {
+            let (collection_id, new_issuer): (u32, AccountId) = decode_input(func, input);
+            let collection = state
+                .collections
+                .get_mut(collection_id as usize)
+                .ok_or(StatusCode::CollectionUnknown)?;
+            if collection.issuer != signer {
+                return Err(StatusCode::NoPermission)
+            }
+            collection.issuer = new_issuer;
+            Ok(Vec::new())
+        }
+
+        RmrkFunc::AcceptNft
+        | RmrkFunc::RejectNft
+        | RmrkFunc::AddBasicResource
+        | RmrkFunc::AddComposableResource
+        | RmrkFunc::AddSlotResource
+        | RmrkFunc::AcceptResource
+        | RmrkFunc::RemoveResource
+        | RmrkFunc::AcceptResourceRemoval
+        | RmrkFunc::SetPriority
+        | RmrkFunc::CreateBase
+        | RmrkFunc::ThemeAdd
+        | RmrkFunc::Equip
+        | RmrkFunc::Unequip
+        | RmrkFunc::Equippable
+        | RmrkFunc::ChangeBaseIssuer => Err(StatusCode::Failed),
+    }
+}
@@ -1,29 +1,109 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use scale::{Encode, Decode, HasCompact};
-use ink_env::Environment;
+use ink_env::{AccountId, Environment};
 use ink_lang as ink;
+use ink_prelude::vec::Vec;
 
+pub use dapps_staking_chain_extension_types::StakingOrigin;
+use dapps_staking_chain_extension_types::StatusCode;
+
+type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+
+/// Chain extension for `pallet_dapps_staking`.
+///
+/// The IDs must match `dapps_staking_chain_extension_types::DappsStakingFunc`.
 #[ink::chain_extension]
 pub trait DappsStakingExt {
     type ErrorCode = DSErrorCode;
 
+    /// `DappsStakingFunc::CurrentEra`
     #[ink(extension = 3401, returns_result = false)]
     fn read_current_era() -> u32;
 
-    #[ink(extension = 3402)]
-    fn read_era_info(
+    /// `DappsStakingFunc::GeneralEraInfo`
+    ///
+    /// The runtime writes the plain SCALE encoded `EraInfo` on success and returns
+    /// `StatusCode::EraNotFound` with an empty output for an era without info.
+    #[ink(extension = 3402, returns_result = false)]
+    fn read_era_info(era: u32) -> EraInfo<Balance>;
+
+    /// `DappsStakingFunc::BondAndStake`
+    #[ink(extension = 3403, returns_result = false)]
+    fn bond_and_stake(
+        origin: StakingOrigin,
+        contract_id: SmartContract<AccountId>,
+        value: Balance,
+    );
+
+    /// `DappsStakingFunc::UnbondAndUnstake`
+    #[ink(extension = 3404, returns_result = false)]
+    fn unbond_and_unstake(
+        origin: StakingOrigin,
+        contract_id: SmartContract<AccountId>,
+        value: Balance,
+    );
+
+    /// `DappsStakingFunc::WithdrawUnbonded`
+    #[ink(extension = 3405, returns_result = false)]
+    fn withdraw_unbonded(origin: StakingOrigin);
+
+    /// `DappsStakingFunc::ClaimStaker`, returns the claimed amount
+    #[ink(extension = 3406, returns_result = false)]
+    fn claim_staker(origin: StakingOrigin, contract_id: SmartContract<AccountId>) -> Balance;
+
+    /// `DappsStakingFunc::ClaimDapp`, returns the claimed amount
+    #[ink(extension = 3407, returns_result = false)]
+    fn claim_dapp(
+        origin: StakingOrigin,
+        contract_id: SmartContract<AccountId>,
+        era: u32,
+    ) -> Balance;
+
+    /// `DappsStakingFunc::StakedAmount`
+    #[ink(extension = 3408, returns_result = false)]
+    fn read_staked_amount(account: AccountId, contract_id: SmartContract<AccountId>) -> Balance;
+
+    /// `DappsStakingFunc::ContractStake`
+    #[ink(extension = 3409, returns_result = false)]
+    fn read_contract_stake(
+        contract_id: SmartContract<AccountId>,
         era: u32,
-    ) -> Result<EraInfo<<ink_env::DefaultEnvironment as Environment>::Balance>, DSError>;
+    ) -> Option<ContractStakeInfo<Balance>>;
+
+    /// `DappsStakingFunc::UnbondingInfo`
+    #[ink(extension = 3410, returns_result = false)]
+    fn read_unbonding_info(account: AccountId) -> Vec<UnlockingChunk<Balance>>;
+
+    /// `DappsStakingFunc::Register`
+    #[ink(extension = 3411, returns_result = false)]
+    fn register(origin: StakingOrigin, contract_id: SmartContract<AccountId>);
 }
 
-#[derive(scale::Encode, scale::Decode)]
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum DSErrorCode {
+    /// The runtime failed to execute the call
     Failed,
+    /// The runtime returned a status code this contract does not know about
+    UnknownStatus(u32),
+    /// The runtime output could not be decoded
+    DecodeFailed,
+    /// The requested era has no recorded info
+    EraNotFound,
+    /// The contract is not registered for dapps staking
+    NotRegistered,
+    /// The contract is already registered for dapps staking
+    AlreadyRegistered,
+    /// The account has nothing staked on the contract
+    NotStaked,
+    /// There are no unbonded chunks ready to be withdrawn
+    NothingToWithdraw,
+    /// The value is below the minimum or above the available balance
+    InsufficientValue,
 }
 
-#[derive(scale::Encode, scale::Decode)]
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum DSError {
     ErrorCode(DSErrorCode),
@@ -37,16 +117,22 @@ impl From<DSErrorCode> for DSError {
 
 impl From<scale::Error> for DSError {
     fn from(_: scale::Error) -> Self {
-        panic!("encountered unexpected invalid SCALE encoding")
+        Self::ErrorCode(DSErrorCode::DecodeFailed)
     }
 }
 
 impl ink_env::chain_extension::FromStatusCode for DSErrorCode {
     fn from_status_code(status_code: u32) -> Result<(), Self> {
-        match status_code {
-            0 => Ok(()),
-            1 => Err(Self::Failed),
-            _ => panic!("encountered unknown status code"),
+        match StatusCode::try_from(status_code) {
+            Ok(StatusCode::Success) => Ok(()),
+            Ok(StatusCode::Failed) => Err(Self::Failed),
+            Ok(StatusCode::EraNotFound) => Err(Self::EraNotFound),
+            Ok(StatusCode::NotRegistered) => Err(Self::NotRegistered),
+            Ok(StatusCode::AlreadyRegistered) => Err(Self::AlreadyRegistered),
+            Ok(StatusCode::NotStaked) => Err(Self::NotStaked),
+            Ok(StatusCode::NothingToWithdraw) => Err(Self::NothingToWithdraw),
+            Ok(StatusCode::InsufficientValue) => Err(Self::InsufficientValue),
+            Err(unknown) => Err(Self::UnknownStatus(unknown)),
         }
     }
 }
@@ -77,6 +163,42 @@ pub struct EraInfo<Balance: HasCompact> {
     pub locked: Balance,
 }
 
+/// A record of the total amount staked on a contract in an era
+#[derive(PartialEq, Debug, Eq, Clone, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ContractStakeInfo<Balance: HasCompact> {
+    /// Total staked amount
+    #[codec(compact)]
+    pub total: Balance,
+    /// Total number of active stakers
+    #[codec(compact)]
+    pub number_of_stakers: u32,
+    /// Indicates whether rewards were claimed for this era or not
+    pub contract_reward_claimed: bool,
+}
+
+/// A chunk of funds being unbonded
+#[derive(PartialEq, Debug, Eq, Clone, Copy, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct UnlockingChunk<Balance: HasCompact> {
+    /// Amount being unlocked
+    #[codec(compact)]
+    pub amount: Balance,
+    /// Era in which the amount will become unlocked and can be withdrawn
+    #[codec(compact)]
+    pub unlock_era: u32,
+}
+
+/// Multi-VM pointer to smart contract instance, as registered in dapps staking
+#[derive(PartialEq, Debug, Eq, Copy, Clone, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SmartContract<AccountId> {
+    /// EVM smart contract instance
+    Evm([u8; 20]),
+    /// Wasm smart contract instance
+    Wasm(AccountId),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum CustomEnvironment {}
@@ -95,8 +217,17 @@ impl Environment for CustomEnvironment {
 
 #[ink::contract(env = crate::CustomEnvironment)]
 mod dapp_staking_extension {
-    use super::{DSError, EraInfo};
+    use super::{
+        ContractStakeInfo, DSError, EraInfo, SmartContract, StakingOrigin, UnlockingChunk,
+    };
+    use ink_prelude::vec::Vec;
 
+    /// Staking messages take a `StakingOrigin`: `Caller` acts for the caller, with the
+    /// caller's funds, `Address` for the contract, with the funds it holds. The runtime
+    /// only lets the contract sign with its caller once governance put its code hash in
+    /// `CallerSigningContracts`, until then those calls trap.
+    ///
+    /// Claims act for the caller, `register` for the contract.
     #[ink(storage)]
     pub struct DappsStakingExtension {}
 
@@ -106,6 +237,13 @@ mod dapp_staking_extension {
         new: u32,
     }
 
+    #[ink(event)]
+    pub struct RewardClaimed {
+        #[ink(topic)]
+        contract_id: SmartContract<AccountId>,
+        amount: Balance,
+    }
+
     impl DappsStakingExtension {
         #[ink(constructor)]
         pub fn new() -> Self {
@@ -122,18 +260,134 @@ mod dapp_staking_extension {
         }
 
         /// Calls general_era_info() in the pallet-dapps-staking
+        ///
+        /// Fails with `DSErrorCode::EraNotFound` for an era without info, e.g. a future one.
         #[ink(message)]
         pub fn read_era_info(&self, era: u32) -> Result<EraInfo<Balance>, DSError> {
-            ink_env::debug_println!("read_era_info: entered");
-            self.env().extension().read_era_info(era)
+            let era_info = self.env().extension().read_era_info(era)?;
+            ink_env::debug_println!("read_era_info: staked:{:?}", era_info.staked);
+            Ok(era_info)
+        }
+
+        /// Calls bond_and_stake() in the pallet-dapps-staking for `origin`
+        #[ink(message)]
+        pub fn bond_and_stake(
+            &mut self,
+            origin: StakingOrigin,
+            contract_id: SmartContract<AccountId>,
+            value: Balance,
+        ) -> Result<(), DSError> {
+            self.env()
+                .extension()
+                .bond_and_stake(origin, contract_id, value)?;
+            Ok(())
+        }
+
+        /// Calls unbond_and_unstake() in the pallet-dapps-staking for `origin`
+        #[ink(message)]
+        pub fn unbond_and_unstake(
+            &mut self,
+            origin: StakingOrigin,
+            contract_id: SmartContract<AccountId>,
+            value: Balance,
+        ) -> Result<(), DSError> {
+            self.env()
+                .extension()
+                .unbond_and_unstake(origin, contract_id, value)?;
+            Ok(())
+        }
+
+        /// Calls withdraw_unbonded() in the pallet-dapps-staking for `origin`
+        #[ink(message)]
+        pub fn withdraw_unbonded(&mut self, origin: StakingOrigin) -> Result<(), DSError> {
+            self.env()
+                .extension()
+                .withdraw_unbonded(origin)?;
+            Ok(())
+        }
+
+        /// Calls claim_staker() in the pallet-dapps-staking for the caller
+        ///
+        /// Claims the oldest unclaimed era of the caller on `contract_id`.
+        #[ink(message)]
+        pub fn claim_staker(
+            &mut self,
+            contract_id: SmartContract<AccountId>,
+        ) -> Result<Balance, DSError> {
+            let amount = self
+                .env()
+                .extension()
+                .claim_staker(StakingOrigin::Caller, contract_id)?;
+            self.env().emit_event(RewardClaimed { contract_id, amount });
+            Ok(amount)
+        }
+
+        /// Calls claim_dapp() in the pallet-dapps-staking for the caller
+        ///
+        /// The reward goes to the developer of `contract_id`, whoever claims it.
+        #[ink(message)]
+        pub fn claim_dapp(
+            &mut self,
+            contract_id: SmartContract<AccountId>,
+            era: u32,
+        ) -> Result<Balance, DSError> {
+            let amount = self
+                .env()
+                .extension()
+                .claim_dapp(StakingOrigin::Caller, contract_id, era)?;
+            self.env().emit_event(RewardClaimed { contract_id, amount });
+            Ok(amount)
+        }
 
-            // let era_info_result: Result<EraInfo<Balance>, _> = self.env().extension().read_era_info(era);
-            // let era_info = match era_info_result{
-            //     Ok(info)  => info,
-            //     Err(e) => return Err(e),
-            // };
-            // ink_env::debug_println!("read_era_info: staked:{:?}", era_info.rewards.stakers);
-            // Ok(era_info)
+        /// Calls register() in the pallet-dapps-staking for this contract
+        ///
+        /// The contract is its own developer, so it must hold the register deposit.
+        #[ink(message)]
+        pub fn register(&mut self) -> Result<(), DSError> {
+            let contract_id = SmartContract::Wasm(self.env().account_id());
+            self.env()
+                .extension()
+                .register(StakingOrigin::Address, contract_id)?;
+            Ok(())
+        }
+
+        /// Calls staker_info() in the pallet-dapps-staking
+        ///
+        /// Returns the amount `account` currently has staked on `contract_id`.
+        #[ink(message)]
+        pub fn read_staked_amount(
+            &self,
+            account: AccountId,
+            contract_id: SmartContract<AccountId>,
+        ) -> Result<Balance, DSError> {
+            Ok(self
+                .env()
+                .extension()
+                .read_staked_amount(account, contract_id)?)
+        }
+
+        /// Calls contract_stake_info() in the pallet-dapps-staking
+        #[ink(message)]
+        pub fn read_contract_stake(
+            &self,
+            contract_id: SmartContract<AccountId>,
+            era: u32,
+        ) -> Result<Option<ContractStakeInfo<Balance>>, DSError> {
+            Ok(self
+                .env()
+                .extension()
+                .read_contract_stake(contract_id, era)?)
+        }
+
+        /// Calls ledger() in the pallet-dapps-staking
+        ///
+        /// Returns the unlocking chunks of `account`.
+        #[ink(message)]
+        pub fn read_unbonding_info(
+            &self,
+            account: AccountId,
+        ) -> Result<Vec<UnlockingChunk<Balance>>, DSError> {
+            Ok(self.env().extension().read_unbonding_info(account)?)
         }
     }
 
@@ -142,48 +396,213 @@ mod dapp_staking_extension {
     mod tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
+        use crate::{DSErrorCode, RewardInfo};
+        use chain_extension_mock::{MockRuntime, Origins};
+        use dapps_staking_chain_extension_types::{DappsStakingFunc, StatusCode};
+        use ink_env::chain_extension::FromStatusCode;
         use ink_lang as ink;
 
-        /// We test if the default constructor does its job.
-        #[ink::test]
-        fn default_works() {
-            let ds_extension = DappsStakingExtension::default();
-            assert_eq!(ds_extension.get_current_era(), 0);
+        #[test]
+        fn status_codes_convert_without_panicking() {
+            assert_eq!(DSErrorCode::from_status_code(0), Ok(()));
+            assert_eq!(DSErrorCode::from_status_code(2), Err(DSErrorCode::EraNotFound));
+            assert_eq!(
+                DSErrorCode::from_status_code(42),
+                Err(DSErrorCode::UnknownStatus(42))
+            );
+        }
+
+        /// The layout written by the runtime for `pallet_dapps_staking::EraInfo`.
+        #[test]
+        fn era_info_encoding_round_trips() {
+            let era_info = EraInfo::<Balance> {
+                rewards: RewardInfo { stakers: 3, dapps: 2 },
+                staked: 7,
+                locked: 10,
+            };
+            let encoded = scale::Encode::encode(&era_info);
+            // Every field is a single byte compact: stakers, dapps, staked, locked.
+            assert_eq!(encoded, vec![3 << 2, 2 << 2, 7 << 2, 10 << 2]);
+            assert_eq!(scale::Decode::decode(&mut &encoded[..]), Ok(era_info));
         }
 
         #[ink::test]
-        fn chain_extension_works() {
-            // given
+        fn read_era_info_works() {
             struct MockedExtension;
             impl ink_env::test::ChainExtension for MockedExtension {
-                /// The static function id of the chain extension.
                 fn func_id(&self) -> u32 {
-                    2001
+                    DappsStakingFunc::GeneralEraInfo.into()
                 }
 
-                /// The chain extension is called with the given input.
-                ///
-                /// Returns an error code and may fill the `output` buffer with a
-                /// SCALE encoded result. The error code is taken from the
-                /// `ink_env::chain_extension::FromStatusCode` implementation for
-                /// `DappsStakingResponseError`.
-                fn call(&mut self, _input: &[u8], output: &mut Vec<u8>) -> u32 {
-                    let ret: u32 = 1;
-                    scale::Encode::encode_to(&ret, output);
-                    0
+                fn call(&mut self, mut input: &[u8], output: &mut Vec<u8>) -> u32 {
+                    // The off-chain engine hands over the encoded input as a `Vec<u8>`.
+                    let input: Vec<u8> = scale::Decode::decode(&mut input).unwrap();
+                    let era: u32 = scale::Decode::decode(&mut &input[..]).unwrap();
+                    if era > 1 {
+                        return StatusCode::EraNotFound.into();
+                    }
+                    let era_info = EraInfo::<Balance> {
+                        staked: 70,
+                        ..Default::default()
+                    };
+                    scale::Encode::encode_to(&era_info, output);
+                    StatusCode::Success.into()
                 }
             }
             ink_env::test::register_chain_extension(MockedExtension);
-            let mut ds_extension = DappsStakingExtension::default();
-            assert_eq!(ds_extension.get_current_era(), 0);
+            let ds_extension = DappsStakingExtension::new();
+
+            assert_eq!(ds_extension.read_era_info(1).map(|info| info.staked), Ok(70));
+            assert_eq!(
+                ds_extension.read_era_info(2),
+                Err(DSError::ErrorCode(DSErrorCode::EraNotFound))
+            );
+        }
+
+        #[test]
+        fn invalid_encoding_is_an_error() {
+            let decoded: Result<u32, _> = scale::Decode::decode(&mut &[0u8][..]);
+            assert_eq!(
+                DSError::from(decoded.unwrap_err()),
+                DSError::ErrorCode(DSErrorCode::DecodeFailed)
+            );
+        }
+
+        /// We test if the default constructor does its job.
+        #[ink::test]
+        fn default_works() {
+            let _runtime = MockRuntime::new();
+            let ds_extension = DappsStakingExtension::new();
+            assert_eq!(ds_extension.read_current_era(), Ok(0));
+        }
+
+        #[ink::test]
+        fn chain_extension_works() {
+            // given
+            let runtime = MockRuntime::new();
+            runtime.dapps_staking().current_era = 1;
+            let ds_extension = DappsStakingExtension::new();
 
             // when
-            ds_extension
+            let era = ds_extension
                 .read_current_era()
                 .expect("read_current_era must work");
 
             // then
-            assert_eq!(ds_extension.get_current_era(), 1);
+            assert_eq!(era, 1);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn staking_round_trip_works() {
+            let runtime = MockRuntime::new();
+            {
+                let mut staking = runtime.dapps_staking();
+                staking.minimum_stake = 10;
+                staking.unbonding_period = 2;
+                staking.staker_reward = 3;
+            }
+            let mut ds_extension = DappsStakingExtension::new();
+            let contract_id = SmartContract::Wasm(runtime.origins().address);
+            let staker = runtime.origins().caller;
+            let error = |code| Err(DSError::ErrorCode(code));
+
+            assert_eq!(
+                ds_extension.bond_and_stake(StakingOrigin::Caller, contract_id, 10),
+                error(DSErrorCode::NotRegistered)
+            );
+            assert_eq!(ds_extension.register(), Ok(()));
+            assert_eq!(ds_extension.register(), error(DSErrorCode::AlreadyRegistered));
+            assert_eq!(
+                ds_extension.bond_and_stake(StakingOrigin::Caller, contract_id, 5),
+                error(DSErrorCode::InsufficientValue)
+            );
+            assert_eq!(
+                ds_extension.bond_and_stake(StakingOrigin::Caller, contract_id, 10),
+                Ok(())
+            );
+            assert_eq!(ds_extension.read_staked_amount(staker, contract_id), Ok(10));
+            assert_eq!(
+                ds_extension
+                    .read_contract_stake(contract_id, 0)
+                    .map(|stake| stake.map(|stake| stake.total)),
+                Ok(Some(10))
+            );
+            assert_eq!(ds_extension.claim_staker(contract_id), Ok(3));
+
+            assert_eq!(
+                ds_extension.unbond_and_unstake(StakingOrigin::Caller, contract_id, 4),
+                Ok(())
+            );
+            assert_eq!(
+                ds_extension.read_unbonding_info(staker).map(|chunks| chunks[0].unlock_era),
+                Ok(2)
+            );
+            assert_eq!(
+                ds_extension.withdraw_unbonded(StakingOrigin::Caller),
+                error(DSErrorCode::NothingToWithdraw)
+            );
+            runtime.dapps_staking().current_era = 2;
+            assert_eq!(ds_extension.withdraw_unbonded(StakingOrigin::Caller), Ok(()));
+            assert_eq!(ds_extension.read_unbonding_info(staker), Ok(Vec::new()));
+        }
+
+        #[ink::test]
+        fn contract_stakes_with_its_own_funds() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.django);
+            let runtime = MockRuntime::new();
+            {
+                let mut staking = runtime.dapps_staking();
+                staking.minimum_stake = 10;
+                staking.unbonding_period = 1;
+            }
+            let mut ds_extension = DappsStakingExtension::new();
+            let Origins { caller, address } = runtime.origins();
+            let contract_id = SmartContract::Wasm(address);
+            assert_eq!(ds_extension.register(), Ok(()));
+
+            assert_eq!(
+                ds_extension.bond_and_stake(StakingOrigin::Address, contract_id, 10),
+                Ok(())
+            );
+            assert_eq!(ds_extension.read_staked_amount(address, contract_id), Ok(10));
+            assert_eq!(ds_extension.read_staked_amount(caller, contract_id), Ok(0));
+
+            assert_eq!(
+                ds_extension.unbond_and_unstake(StakingOrigin::Address, contract_id, 10),
+                Ok(())
+            );
+            assert_eq!(
+                ds_extension.read_unbonding_info(address).map(|chunks| chunks.len()),
+                Ok(1)
+            );
+            assert_eq!(ds_extension.read_unbonding_info(caller), Ok(Vec::new()));
+            runtime.dapps_staking().current_era = 1;
+            assert_eq!(
+                ds_extension.withdraw_unbonded(StakingOrigin::Caller),
+                Err(DSError::ErrorCode(DSErrorCode::NothingToWithdraw))
+            );
+            assert_eq!(ds_extension.withdraw_unbonded(StakingOrigin::Address), Ok(()));
+            assert_eq!(ds_extension.read_unbonding_info(address), Ok(Vec::new()));
+        }
+
+        #[ink::test]
+        fn scripted_status_codes_are_reported() {
+            let runtime = MockRuntime::new();
+            let mut ds_extension = DappsStakingExtension::new();
+            runtime.fail_next(DappsStakingFunc::Register, StatusCode::Failed);
+            runtime.fail_next(DappsStakingFunc::Register, 42u32);
+
+            assert_eq!(
+                ds_extension.register(),
+                Err(DSError::ErrorCode(DSErrorCode::Failed))
+            );
+            assert_eq!(
+                ds_extension.register(),
+                Err(DSError::ErrorCode(DSErrorCode::UnknownStatus(42)))
+            );
+            assert_eq!(ds_extension.register(), Ok(()));
         }
     }
 }
@@ -0,0 +1,315 @@
+//! Weights for the calls dispatched by `LocalChainExtension`.
+//!
+//! There is no benchmark for the chain extension itself, and the RMRK pallets of this
+//! runtime come without benchmarked weights. Calls which dispatch a `pallet_dapps_staking`
+//! or `pallet_assets` extrinsic reuse the pallet's own benchmarked weights. The
+//! `pallet_rmrk_core` calls wrapping a `pallet_uniques` operation are charged its
+//! benchmarked weight plus the RMRK storage reads and writes around it.
+//!
+//! The remaining base costs, of the read-only calls and of the `pallet_rmrk_equip` calls,
+//! are hand-estimated placeholders plus the database reads and writes the call makes, and
+//! want replacing by benchmarked ones before the extension goes to a production chain.
+//! Calls writing a list of items scale with its length. Copying the input and output
+//! buffers between the contract and the runtime is charged per byte, at the benchmarked
+//! cost `pallet_contracts` charges for `seal_input` and `seal_return`.
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `LocalChainExtension`.
+pub trait WeightInfo {
+    fn random() -> Weight;
+    fn current_era() -> Weight;
+    fn general_era_info() -> Weight;
+    fn bond_and_stake() -> Weight;
+    fn unbond_and_unstake() -> Weight;
+    fn withdraw_unbonded() -> Weight;
+    fn claim_staker() -> Weight;
+    fn claim_dapp() -> Weight;
+    fn staked_amount() -> Weight;
+    fn contract_stake() -> Weight;
+    fn unbonding_info() -> Weight;
+    fn register() -> Weight;
+    fn next_nft_id() -> Weight;
+    fn collection_index() -> Weight;
+    fn collection_info() -> Weight;
+    fn nft_info() -> Weight;
+    fn children(n: u32) -> Weight;
+    fn properties(n: u32) -> Weight;
+    fn balance_of(n: u32) -> Weight;
+    fn mint_nft(r: u32) -> Weight;
+    fn create_collection() -> Weight;
+    fn burn_nft() -> Weight;
+    fn send() -> Weight;
+    fn accept_nft() -> Weight;
+    fn reject_nft() -> Weight;
+    fn change_collection_issuer() -> Weight;
+    fn add_basic_resource() -> Weight;
+    fn add_composable_resource() -> Weight;
+    fn add_slot_resource() -> Weight;
+    fn accept_resource() -> Weight;
+    fn remove_resource() -> Weight;
+    fn accept_resource_removal() -> Weight;
+    fn set_priority(n: u32) -> Weight;
+    fn create_base(p: u32) -> Weight;
+    fn theme_add(p: u32) -> Weight;
+    fn equip() -> Weight;
+    fn unequip() -> Weight;
+    fn equippable() -> Weight;
+    fn change_base_issuer() -> Weight;
+    fn assets_create() -> Weight;
+    fn assets_mint() -> Weight;
+    fn assets_burn() -> Weight;
+    fn assets_transfer() -> Weight;
+    fn assets_approve() -> Weight;
+    fn assets_transfer_approved() -> Weight;
+    fn assets_balance_of() -> Weight;
+    fn assets_total_supply() -> Weight;
+    fn assets_metadata() -> Weight;
+    fn assets_allowance() -> Weight;
+    fn per_byte() -> Weight;
+}
+
+/// Weights for `LocalChainExtension`, partly hand-estimated, see the module docs.
+pub struct EstimatedWeight<T>(PhantomData<T>);
+impl<T> WeightInfo for EstimatedWeight<T>
+where
+    T: frame_system::Config
+        + pallet_contracts::Config
+        + pallet_dapps_staking::Config
+        + pallet_assets::Config
+        + pallet_uniques::Config,
+{
+    fn random() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn current_era() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn general_era_info() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn bond_and_stake() -> Weight {
+        <T as pallet_dapps_staking::Config>::WeightInfo::bond_and_stake()
+    }
+    fn unbond_and_unstake() -> Weight {
+        <T as pallet_dapps_staking::Config>::WeightInfo::unbond_and_unstake()
+    }
+    fn withdraw_unbonded() -> Weight {
+        <T as pallet_dapps_staking::Config>::WeightInfo::withdraw_unbonded()
+    }
+    fn claim_staker() -> Weight {
+        // The restaking path is the more expensive one, the read is the balance of the
+        // pallet account, which pays the reward.
+        <T as pallet_dapps_staking::Config>::WeightInfo::claim_staker_with_restake()
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn claim_dapp() -> Weight {
+        // The read is the balance of the pallet account, which pays the reward.
+        <T as pallet_dapps_staking::Config>::WeightInfo::claim_dapp()
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn staked_amount() -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn contract_stake() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn unbonding_info() -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn register() -> Weight {
+        <T as pallet_dapps_staking::Config>::WeightInfo::register()
+    }
+    fn next_nft_id() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn collection_index() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn collection_info() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn nft_info() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn children(n: u32) -> Weight {
+        // The output is charged per byte on top.
+        (10_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(n as Weight))
+    }
+    fn properties(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(n as Weight))
+    }
+    fn balance_of(n: u32) -> Weight {
+        (10_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(n as Weight))
+    }
+    fn mint_nft(r: u32) -> Weight {
+        // Collection, next NFT ID and NFT, then a read and a write for each resource.
+        <T as pallet_uniques::Config>::WeightInfo::mint()
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn create_collection() -> Weight {
+        // Collection index and collection.
+        <T as pallet_uniques::Config>::WeightInfo::create()
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn burn_nft() -> Weight {
+        // Collection, NFT, its resources and its place in the parent's children.
+        <T as pallet_uniques::Config>::WeightInfo::burn()
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn send() -> Weight {
+        // NFT, lock, and the children of the old and the new parent.
+        <T as pallet_uniques::Config>::WeightInfo::transfer()
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn accept_nft() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn reject_nft() -> Weight {
+        (55_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn change_collection_issuer() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn add_basic_resource() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn add_composable_resource() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn add_slot_resource() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn accept_resource() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_resource() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn accept_resource_removal() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_priority(n: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn create_base(p: u32) -> Weight {
+        // Next base ID and base, then a write for each part.
+        (40_000_000 as Weight)
+            .saturating_add((2_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn theme_add(p: u32) -> Weight {
+        // Base and its default theme, then a write for each property.
+        (30_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn equip() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn unequip() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn equippable() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn change_base_issuer() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn assets_create() -> Weight {
+        <T as pallet_assets::Config>::WeightInfo::create()
+    }
+    fn assets_mint() -> Weight {
+        <T as pallet_assets::Config>::WeightInfo::mint()
+    }
+    fn assets_burn() -> Weight {
+        <T as pallet_assets::Config>::WeightInfo::burn()
+    }
+    fn assets_transfer() -> Weight {
+        <T as pallet_assets::Config>::WeightInfo::transfer()
+    }
+    fn assets_approve() -> Weight {
+        // An existing approval is cancelled before the new one is placed.
+        <T as pallet_assets::Config>::WeightInfo::cancel_approval()
+            .saturating_add(<T as pallet_assets::Config>::WeightInfo::approve_transfer())
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn assets_transfer_approved() -> Weight {
+        <T as pallet_assets::Config>::WeightInfo::transfer_approved()
+    }
+    fn assets_balance_of() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn assets_total_supply() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn assets_metadata() -> Weight {
+        (6_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn assets_allowance() -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+    fn per_byte() -> Weight {
+        let host_fn_weights = <T as pallet_contracts::Config>::Schedule::get().host_fn_weights;
+        host_fn_weights.input_per_byte.max(host_fn_weights.return_per_byte)
+    }
+}
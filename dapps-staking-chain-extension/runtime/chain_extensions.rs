@@ -0,0 +1,129 @@
+//! The families of functions `LocalChainExtension` answers for.
+//!
+//! Each family implements `RuntimeExtension` for its own range of function IDs:
+//! randomness 11xx, dapps staking 34xx, RMRK 35xx and assets 36xx. A tuple of
+//! extensions is an extension as well, answering with the member owning the ID,
+//! so the runtime enables a family by listing it in `RuntimeExtensions`.
+//!
+//! Governance may still disable a family, or limit its privileged functions to
+//! some contracts, see `LocalChainExtension::ensure_permitted`. Signing with the
+//! caller of a contract is denied unless the contract is in `CallerSigningContracts`.
+
+mod assets;
+mod dapps_staking;
+mod randomness;
+mod rmrk;
+
+pub use assets::AssetsExtension;
+pub use dapps_staking::DappsStakingExtension;
+pub use randomness::{RandomnessExtension, RANDOM};
+pub use rmrk::{RmrkExtension, UnboundedResource};
+
+use crate::AccountId;
+use codec::Decode;
+use frame_support::{log::error, weights::Weight};
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+/// SCALE encoded output of a chain extension call, or the status code of an expected failure.
+pub type CallOutcome = Result<Vec<u8>, u32>;
+
+/// Accounts the calling contract may sign dispatchables with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origins {
+    /// The account calling the contract, `None` unless the contract is in
+    /// `CallerSigningContracts`
+    pub caller: Option<AccountId>,
+    /// The contract itself
+    pub address: AccountId,
+}
+
+impl Origins {
+    /// The caller to sign with, trapping a contract not allowed to act for its caller.
+    pub fn caller_signer(&self) -> Result<AccountId, DispatchError> {
+        self.caller
+            .clone()
+            .ok_or(DispatchError::Other("ChainExtension caller origin not allowed"))
+    }
+}
+
+/// A family of chain extension functions, owning a range of function IDs.
+pub trait RuntimeExtension {
+    /// Whether `func_id` falls into the range of this extension.
+    fn owns(func_id: u32) -> bool;
+
+    /// Weight charged for `func_id` once its SCALE encoded `input` is read, before it is
+    /// dispatched. Functions writing a list of items scale with the length in `input`.
+    ///
+    /// Zero for an ID the extension does not declare, `dispatch` rejects those.
+    fn weight(func_id: u32, input: &[u8]) -> Weight;
+
+    /// Whether `func_id` acts with authority, e.g. moves funds or issues assets, and is
+    /// therefore limited to the contracts in `PrivilegedContracts`.
+    fn is_privileged(func_id: u32) -> bool;
+
+    /// Executes `func_id` with the SCALE encoded `input` and returns the SCALE encoded output.
+    ///
+    /// Expected failures are reported as a status code, an `Err` traps the contract.
+    fn dispatch(func_id: u32, input: &[u8], origins: &Origins)
+        -> Result<CallOutcome, DispatchError>;
+}
+
+/// Implements `RuntimeExtension` for a tuple, the first member owning an ID answers for it.
+macro_rules! impl_runtime_extension_for_tuple {
+    ($($member:ident),+) => {
+        impl<$($member: RuntimeExtension),+> RuntimeExtension for ($($member,)+) {
+            fn owns(func_id: u32) -> bool {
+                $($member::owns(func_id))||+
+            }
+
+            fn weight(func_id: u32, input: &[u8]) -> Weight {
+                $(
+                    if $member::owns(func_id) {
+                        return $member::weight(func_id, input);
+                    }
+                )+
+                0
+            }
+
+            fn is_privileged(func_id: u32) -> bool {
+                $(
+                    if $member::owns(func_id) {
+                        return $member::is_privileged(func_id);
+                    }
+                )+
+                false
+            }
+
+            fn dispatch(
+                func_id: u32,
+                input: &[u8],
+                origins: &Origins,
+            ) -> Result<CallOutcome, DispatchError> {
+                $(
+                    if $member::owns(func_id) {
+                        return $member::dispatch(func_id, input, origins);
+                    }
+                )+
+                Err(unknown_func_id(func_id))
+            }
+        }
+    };
+}
+
+impl_runtime_extension_for_tuple!(A);
+impl_runtime_extension_for_tuple!(A, B);
+impl_runtime_extension_for_tuple!(A, B, C);
+impl_runtime_extension_for_tuple!(A, B, C, D);
+impl_runtime_extension_for_tuple!(A, B, C, D, E);
+impl_runtime_extension_for_tuple!(A, B, C, D, E, F);
+
+/// The error trapping a contract which called an ID no extension declares.
+fn unknown_func_id(func_id: u32) -> DispatchError {
+    error!("Called an unregistered `func_id`: {:}", func_id);
+    DispatchError::Other("Unimplemented func_id")
+}
+
+fn decode_input<T: Decode>(input: &mut &[u8]) -> Result<T, DispatchError> {
+    T::decode(input).map_err(|_| DispatchError::Other("ChainExtension failed to decode input"))
+}
@@ -0,0 +1,295 @@
+//! `pallet_assets` behind the `AssetsFunc` IDs, 36xx.
+
+use super::{decode_input, unknown_func_id, CallOutcome, Origins, RuntimeExtension};
+use crate::{
+    chain_extension_weights::WeightInfo as _, AccountId, Assets, Balance, ExtensionWeight,
+    Origin, Runtime,
+};
+use assets_chain_extension_types::{
+    AssetId, AssetsFunc, AssetsOrigin, StatusCode, ASSETS_EXTENSION,
+};
+use codec::Encode;
+use frame_support::{
+    dispatch::DispatchResult,
+    log::trace,
+    traits::fungibles::{approvals, metadata},
+    weights::Weight,
+};
+use sp_runtime::{DispatchError, TokenError};
+use sp_std::prelude::*;
+
+/// Asset writes are signed by the caller of the contract or by the contract itself,
+/// as chosen by the `AssetsOrigin` argument. Only contracts in `CallerSigningContracts`
+/// may sign with their caller.
+pub struct AssetsExtension;
+
+impl RuntimeExtension for AssetsExtension {
+    fn owns(func_id: u32) -> bool {
+        func_id / 100 == ASSETS_EXTENSION / 100
+    }
+
+    fn weight(func_id: u32, _input: &[u8]) -> Weight {
+        match AssetsFunc::try_from(func_id) {
+            Ok(AssetsFunc::Create) => ExtensionWeight::assets_create(),
+            Ok(AssetsFunc::Mint) => ExtensionWeight::assets_mint(),
+            Ok(AssetsFunc::Burn) => ExtensionWeight::assets_burn(),
+            Ok(AssetsFunc::Transfer) => ExtensionWeight::assets_transfer(),
+            Ok(AssetsFunc::Approve) => ExtensionWeight::assets_approve(),
+            Ok(AssetsFunc::TransferApproved) => ExtensionWeight::assets_transfer_approved(),
+            Ok(AssetsFunc::BalanceOf) => ExtensionWeight::assets_balance_of(),
+            Ok(AssetsFunc::TotalSupply) => ExtensionWeight::assets_total_supply(),
+            Ok(AssetsFunc::Metadata) => ExtensionWeight::assets_metadata(),
+            Ok(AssetsFunc::Allowance) => ExtensionWeight::assets_allowance(),
+            Err(_) => 0,
+        }
+    }
+
+    /// The writes, which issue assets or move them.
+    fn is_privileged(func_id: u32) -> bool {
+        matches!(
+            AssetsFunc::try_from(func_id),
+            Ok(AssetsFunc::Create
+                | AssetsFunc::Mint
+                | AssetsFunc::Burn
+                | AssetsFunc::Transfer
+                | AssetsFunc::Approve
+                | AssetsFunc::TransferApproved)
+        )
+    }
+
+    fn dispatch(
+        func_id: u32,
+        input: &[u8],
+        origins: &Origins,
+    ) -> Result<CallOutcome, DispatchError> {
+        let func = AssetsFunc::try_from(func_id).map_err(unknown_func_id)?;
+        let signer = |origin: AssetsOrigin| match origin {
+            AssetsOrigin::Caller => origins.caller_signer(),
+            AssetsOrigin::Address => Ok(origins.address.clone()),
+        };
+        let outcome = Self::call(func, input, signer)?;
+        Ok(outcome.map_err(u32::from))
+    }
+}
+
+impl AssetsExtension {
+    fn call(
+        func: AssetsFunc,
+        mut input: &[u8],
+        signer: impl Fn(AssetsOrigin) -> Result<AccountId, DispatchError>,
+    ) -> Result<Result<Vec<u8>, StatusCode>, DispatchError> {
+        let origin = |origin: AssetsOrigin| signer(origin).map(Origin::signed);
+        match func {
+            AssetsFunc::Create => {
+                let (creator, id, admin, min_balance): (AssetsOrigin, AssetId, AccountId, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, id:{:?}, admin:{:?}, min_balance:{:?}",
+                    func,
+                    creator,
+                    id,
+                    admin,
+                    min_balance
+                );
+                let result = Assets::create(origin(creator)?, id, admin.into(), min_balance);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            AssetsFunc::Mint => {
+                let (issuer, id, beneficiary, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, id:{:?}, beneficiary:{:?}, amount:{:?}",
+                    func,
+                    issuer,
+                    id,
+                    beneficiary,
+                    amount
+                );
+                let result = Assets::mint(origin(issuer)?, id, beneficiary.into(), amount);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            AssetsFunc::Burn => {
+                let (admin, id, who, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, id:{:?}, who:{:?}, amount:{:?}",
+                    func,
+                    admin,
+                    id,
+                    who,
+                    amount
+                );
+                let result = Assets::burn(origin(admin)?, id, who.into(), amount);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            AssetsFunc::Transfer => {
+                let (sender, id, target, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, id:{:?}, target:{:?}, amount:{:?}",
+                    func,
+                    sender,
+                    id,
+                    target,
+                    amount
+                );
+                let result = Assets::transfer(origin(sender)?, id, target.into(), amount);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            AssetsFunc::Approve => {
+                let (owner, id, delegate, amount): (AssetsOrigin, AssetId, AccountId, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, id:{:?}, delegate:{:?}, amount:{:?}",
+                    func,
+                    owner,
+                    id,
+                    delegate,
+                    amount
+                );
+                // `approve_transfer` adds to an existing approval, PSP22 replaces it.
+                let approved = <Assets as approvals::Inspect<AccountId>>::allowance(
+                    id,
+                    &signer(owner)?,
+                    &delegate,
+                );
+                let mut result = Ok(());
+                if approved > 0 {
+                    result = Assets::cancel_approval(origin(owner)?, id, delegate.clone().into());
+                }
+                if result.is_ok() && amount > 0 {
+                    result = Assets::approve_transfer(origin(owner)?, id, delegate.into(), amount);
+                }
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            AssetsFunc::TransferApproved => {
+                let (delegate, id, owner, destination, amount): (
+                    AssetsOrigin,
+                    AssetId,
+                    AccountId,
+                    AccountId,
+                    Balance,
+                ) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, id:{:?}, owner:{:?}, destination:{:?}, amount:{:?}",
+                    func,
+                    delegate,
+                    id,
+                    owner,
+                    destination,
+                    amount
+                );
+                let result = Assets::transfer_approved(
+                    origin(delegate)?,
+                    id,
+                    owner.into(),
+                    destination.into(),
+                    amount,
+                );
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            AssetsFunc::BalanceOf => {
+                let (id, who): (AssetId, AccountId) = decode_input(&mut input)?;
+                let balance = Assets::balance(id, &who);
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} id:{:?}, who:{:?}, balance:{:?}",
+                    func,
+                    id,
+                    who,
+                    balance
+                );
+                Ok(Ok(balance.encode()))
+            }
+
+            AssetsFunc::TotalSupply => {
+                let id: AssetId = decode_input(&mut input)?;
+                let total_supply = Assets::total_supply(id);
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} id:{:?}, total_supply:{:?}",
+                    func,
+                    id,
+                    total_supply
+                );
+                Ok(Ok(total_supply.encode()))
+            }
+
+            AssetsFunc::Metadata => {
+                let id: AssetId = decode_input(&mut input)?;
+                // Empty for an asset without metadata, like the pallet answers.
+                let metadata = (
+                    <Assets as metadata::Inspect<AccountId>>::name(&id),
+                    <Assets as metadata::Inspect<AccountId>>::symbol(&id),
+                    <Assets as metadata::Inspect<AccountId>>::decimals(&id),
+                );
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} id:{:?}, metadata:{:?}",
+                    func,
+                    id,
+                    metadata
+                );
+                Ok(Ok(metadata.encode()))
+            }
+
+            AssetsFunc::Allowance => {
+                let (id, owner, delegate): (AssetId, AccountId, AccountId) =
+                    decode_input(&mut input)?;
+                let allowance =
+                    <Assets as approvals::Inspect<AccountId>>::allowance(id, &owner, &delegate);
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} id:{:?}, owner:{:?}, delegate:{:?}, allowance:{:?}",
+                    func,
+                    id,
+                    owner,
+                    delegate,
+                    allowance
+                );
+                Ok(Ok(allowance.encode()))
+            }
+        }
+    }
+
+    /// Maps a failed `pallet_assets` dispatch to the `StatusCode` reported to the contract.
+    fn status_of(result: DispatchResult) -> Result<(), StatusCode> {
+        use pallet_assets::Error;
+
+        result.map_err(|e| {
+            let is = |expected: Error<Runtime>| e == expected.into();
+            if is(Error::Unknown) || e == DispatchError::Token(TokenError::UnknownAsset) {
+                StatusCode::AssetUnknown
+            } else if is(Error::InUse) {
+                StatusCode::AssetExists
+            } else if is(Error::NoPermission) {
+                StatusCode::NoPermission
+            } else if is(Error::BalanceLow) || e == DispatchError::Token(TokenError::NoFunds) {
+                StatusCode::BalanceLow
+            } else if is(Error::MinBalanceZero)
+                || is(Error::WouldDie)
+                || e == DispatchError::Token(TokenError::BelowMinimum)
+            {
+                StatusCode::BelowMinimum
+            } else if is(Error::Unapproved) {
+                StatusCode::Unapproved
+            } else if is(Error::Frozen) || e == DispatchError::Token(TokenError::Frozen) {
+                StatusCode::Frozen
+            } else {
+                StatusCode::Failed
+            }
+        })
+    }
+}
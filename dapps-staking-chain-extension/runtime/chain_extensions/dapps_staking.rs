@@ -0,0 +1,276 @@
+//! `pallet_dapps_staking` behind the `DappsStakingFunc` IDs, 34xx.
+
+use super::{decode_input, unknown_func_id, CallOutcome, Origins, RuntimeExtension};
+use crate::{
+    chain_extension_weights::WeightInfo as _, AccountId, Balance, Balances, DappsStaking,
+    DappsStakingPalletId, ExtensionWeight, Origin, Runtime, SmartContract,
+};
+use codec::Encode;
+use dapps_staking_chain_extension_types::{
+    DappsStakingFunc, StakingOrigin, StatusCode, DAPPS_STAKING_EXTENSION,
+};
+use frame_support::{dispatch::DispatchResultWithPostInfo, log::trace, weights::Weight};
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use sp_std::prelude::*;
+
+/// Staking writes are signed by the caller of the contract or by the contract itself,
+/// as chosen by the `StakingOrigin` argument. Only contracts in `CallerSigningContracts`
+/// may sign with their caller.
+pub struct DappsStakingExtension;
+
+impl RuntimeExtension for DappsStakingExtension {
+    fn owns(func_id: u32) -> bool {
+        func_id / 100 == DAPPS_STAKING_EXTENSION / 100
+    }
+
+    fn weight(func_id: u32, _input: &[u8]) -> Weight {
+        match DappsStakingFunc::try_from(func_id) {
+            Ok(DappsStakingFunc::CurrentEra) => ExtensionWeight::current_era(),
+            Ok(DappsStakingFunc::GeneralEraInfo) => ExtensionWeight::general_era_info(),
+            Ok(DappsStakingFunc::BondAndStake) => ExtensionWeight::bond_and_stake(),
+            Ok(DappsStakingFunc::UnbondAndUnstake) => ExtensionWeight::unbond_and_unstake(),
+            Ok(DappsStakingFunc::WithdrawUnbonded) => ExtensionWeight::withdraw_unbonded(),
+            Ok(DappsStakingFunc::ClaimStaker) => ExtensionWeight::claim_staker(),
+            Ok(DappsStakingFunc::ClaimDapp) => ExtensionWeight::claim_dapp(),
+            Ok(DappsStakingFunc::StakedAmount) => ExtensionWeight::staked_amount(),
+            Ok(DappsStakingFunc::ContractStake) => ExtensionWeight::contract_stake(),
+            Ok(DappsStakingFunc::UnbondingInfo) => ExtensionWeight::unbonding_info(),
+            Ok(DappsStakingFunc::Register) => ExtensionWeight::register(),
+            Err(_) => 0,
+        }
+    }
+
+    /// The writes, which lock, move or pay out funds.
+    fn is_privileged(func_id: u32) -> bool {
+        matches!(
+            DappsStakingFunc::try_from(func_id),
+            Ok(DappsStakingFunc::BondAndStake
+                | DappsStakingFunc::UnbondAndUnstake
+                | DappsStakingFunc::WithdrawUnbonded
+                | DappsStakingFunc::ClaimStaker
+                | DappsStakingFunc::ClaimDapp
+                | DappsStakingFunc::Register)
+        )
+    }
+
+    fn dispatch(
+        func_id: u32,
+        input: &[u8],
+        origins: &Origins,
+    ) -> Result<CallOutcome, DispatchError> {
+        let func = DappsStakingFunc::try_from(func_id).map_err(unknown_func_id)?;
+        let signer = |origin: StakingOrigin| match origin {
+            StakingOrigin::Caller => origins.caller_signer(),
+            StakingOrigin::Address => Ok(origins.address.clone()),
+        };
+        let outcome = Self::call(func, input, signer)?;
+        Ok(outcome.map_err(u32::from))
+    }
+}
+
+impl DappsStakingExtension {
+    fn call(
+        func: DappsStakingFunc,
+        mut input: &[u8],
+        signer: impl Fn(StakingOrigin) -> Result<AccountId, DispatchError>,
+    ) -> Result<Result<Vec<u8>, StatusCode>, DispatchError> {
+        let origin = |origin: StakingOrigin| signer(origin).map(Origin::signed);
+        match func {
+            DappsStakingFunc::CurrentEra => {
+                let current_era = DappsStaking::current_era();
+                let current_era_encoded = current_era.encode();
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} current_era:{:?}",
+                    func,
+                    &current_era_encoded
+                );
+                Ok(Ok(current_era_encoded))
+            }
+
+            DappsStakingFunc::GeneralEraInfo => {
+                let arg: u32 = decode_input(&mut input)?;
+                let era_info = DappsStaking::general_era_info(arg);
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} era_info:{:?}, arg:{:?}",
+                    func,
+                    era_info,
+                    arg
+                );
+                // `DappsStakingExt::read_era_info` decodes a plain `EraInfo` on success.
+                Ok(era_info
+                    .map(|era_info| era_info.encode())
+                    .ok_or(StatusCode::EraNotFound))
+            }
+
+            DappsStakingFunc::BondAndStake => {
+                let (staker, contract_id, value): (StakingOrigin, SmartContract<AccountId>, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, contract_id:{:?}, value:{:?}",
+                    func,
+                    staker,
+                    contract_id,
+                    value
+                );
+                let result = DappsStaking::bond_and_stake(origin(staker)?, contract_id, value);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            DappsStakingFunc::UnbondAndUnstake => {
+                let (staker, contract_id, value): (StakingOrigin, SmartContract<AccountId>, Balance) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, contract_id:{:?}, value:{:?}",
+                    func,
+                    staker,
+                    contract_id,
+                    value
+                );
+                let result = DappsStaking::unbond_and_unstake(origin(staker)?, contract_id, value);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            DappsStakingFunc::WithdrawUnbonded => {
+                let staker: StakingOrigin = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}",
+                    func,
+                    staker
+                );
+                let result = DappsStaking::withdraw_unbonded(origin(staker)?);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            DappsStakingFunc::ClaimStaker => {
+                let (claimer, contract_id): (StakingOrigin, SmartContract<AccountId>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, contract_id:{:?}",
+                    func,
+                    claimer,
+                    contract_id
+                );
+                let pot = Self::reward_pot();
+                let result = DappsStaking::claim_staker(origin(claimer)?, contract_id);
+                Ok(Self::status_of(result).map(|()| Self::paid_since(pot).encode()))
+            }
+
+            DappsStakingFunc::ClaimDapp => {
+                let (claimer, contract_id, era): (StakingOrigin, SmartContract<AccountId>, u32) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, contract_id:{:?}, era:{:?}",
+                    func,
+                    claimer,
+                    contract_id,
+                    era
+                );
+                let pot = Self::reward_pot();
+                let result = DappsStaking::claim_dapp(origin(claimer)?, contract_id, era);
+                Ok(Self::status_of(result).map(|()| Self::paid_since(pot).encode()))
+            }
+
+            DappsStakingFunc::Register => {
+                let (developer, contract_id): (StakingOrigin, SmartContract<AccountId>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} origin:{:?}, contract_id:{:?}",
+                    func,
+                    developer,
+                    contract_id
+                );
+                let result = DappsStaking::register(origin(developer)?, contract_id);
+                Ok(Self::status_of(result).map(|()| Vec::new()))
+            }
+
+            DappsStakingFunc::StakedAmount => {
+                let (account, contract_id): (AccountId, SmartContract<AccountId>) =
+                    decode_input(&mut input)?;
+                let staked = DappsStaking::staker_info(&account, &contract_id).latest_staked_value();
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} account:{:?}, contract_id:{:?}, staked:{:?}",
+                    func,
+                    account,
+                    contract_id,
+                    staked
+                );
+                Ok(Ok(staked.encode()))
+            }
+
+            DappsStakingFunc::ContractStake => {
+                let (contract_id, era): (SmartContract<AccountId>, u32) =
+                    decode_input(&mut input)?;
+                let contract_stake = DappsStaking::contract_stake_info(&contract_id, era);
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} contract_id:{:?}, era:{:?}, contract_stake:{:?}",
+                    func,
+                    contract_id,
+                    era,
+                    contract_stake
+                );
+                Ok(Ok(contract_stake.encode()))
+            }
+
+            DappsStakingFunc::UnbondingInfo => {
+                let account: AccountId = decode_input(&mut input)?;
+                // `UnbondingInfo` encodes as its inner `Vec<UnlockingChunk>`.
+                let unbonding_info = DappsStaking::ledger(&account).unbonding_info;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} account:{:?}, unbonding_info:{:?}",
+                    func,
+                    account,
+                    unbonding_info
+                );
+                Ok(Ok(unbonding_info.encode()))
+            }
+        }
+    }
+
+    /// Maps a failed `pallet_dapps_staking` dispatch to the `StatusCode` reported to the contract.
+    fn status_of(result: DispatchResultWithPostInfo) -> Result<(), StatusCode> {
+        use pallet_dapps_staking::Error;
+
+        result.map(|_| ()).map_err(|e| {
+            let is = |expected: Error<Runtime>| e.error == expected.into();
+            if is(Error::NotOperatedContract) {
+                StatusCode::NotRegistered
+            } else if is(Error::AlreadyRegisteredContract) {
+                StatusCode::AlreadyRegistered
+            } else if is(Error::NotStakedContract) {
+                StatusCode::NotStaked
+            } else if is(Error::NothingToWithdraw) {
+                StatusCode::NothingToWithdraw
+            } else if is(Error::InsufficientValue) || is(Error::StakingWithNoValue) {
+                StatusCode::InsufficientValue
+            } else if is(Error::UnknownEraReward) || is(Error::EraOutOfBounds) {
+                StatusCode::EraNotFound
+            } else {
+                StatusCode::Failed
+            }
+        })
+    }
+
+    /// Free balance of the `pallet_dapps_staking` account, which pays out the rewards.
+    fn reward_pot() -> Balance {
+        Balances::free_balance(&DappsStakingPalletId::get().into_account())
+    }
+
+    /// Amount paid out of the reward pot since it held `pot`.
+    ///
+    /// The pallet claim calls do not return the paid amount, a claim moves it from the
+    /// pallet account to the staker or developer, whether or not it is restaked.
+    fn paid_since(pot: Balance) -> Balance {
+        pot.saturating_sub(Self::reward_pot())
+    }
+}
@@ -0,0 +1,50 @@
+//! `pallet_randomness_collective_flip` behind function ID 1101.
+
+use super::{decode_input, unknown_func_id, CallOutcome, Origins, RuntimeExtension};
+use crate::{chain_extension_weights::WeightInfo as _, ExtensionWeight, RandomnessCollectiveFlip};
+use codec::Encode;
+use frame_support::{log::trace, traits::Randomness, weights::Weight};
+use sp_runtime::DispatchError;
+
+/// `random(subject)`, the seed and the block number since which it is known.
+pub const RANDOM: u32 = 1101;
+
+/// Random seeds for a subject, which needs no signer.
+pub struct RandomnessExtension;
+
+impl RuntimeExtension for RandomnessExtension {
+    fn owns(func_id: u32) -> bool {
+        func_id / 100 == RANDOM / 100
+    }
+
+    fn weight(func_id: u32, _input: &[u8]) -> Weight {
+        match func_id {
+            RANDOM => ExtensionWeight::random(),
+            _ => 0,
+        }
+    }
+
+    fn is_privileged(_func_id: u32) -> bool {
+        false
+    }
+
+    fn dispatch(
+        func_id: u32,
+        mut input: &[u8],
+        _origins: &Origins,
+    ) -> Result<CallOutcome, DispatchError> {
+        if func_id != RANDOM {
+            return Err(unknown_func_id(func_id));
+        }
+        let arg: [u8; 32] = decode_input(&mut input)?;
+        // The seed together with the block number since which it is known.
+        let random_seed = RandomnessCollectiveFlip::random(&arg);
+        let random_slice = random_seed.encode();
+        trace!(
+            target: "runtime",
+            "[ChainExtension]|call|func_id:{:}",
+            func_id
+        );
+        Ok(Ok(random_slice))
+    }
+}
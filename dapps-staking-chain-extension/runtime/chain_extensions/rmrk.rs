@@ -0,0 +1,811 @@
+//! `pallet_rmrk_core` and `pallet_rmrk_equip` behind the `RmrkFunc` IDs, 35xx.
+
+use super::{decode_input, unknown_func_id, CallOutcome, Origins, RuntimeExtension};
+use crate::{
+    chain_extension_weights::WeightInfo as _, AccountId, Address, ExtensionWeight, KeyLimit,
+    MaxCollectionsEquippablePerPart, MaxPropertiesPerTheme, MaxResourcesOnMint, Origin,
+    PartsLimit, RmrkCore, RmrkEquip, Runtime, UniquesStringLimit,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::DispatchResult, log::trace, traits::Get, weights::Weight, BoundedVec,
+};
+use rmrk_chain_extension_types::{
+    RmrkFunc, StatusCode as RmrkStatusCode, RMRK_BALANCE_LIMIT, RMRK_EXTENSION, RMRK_PAGE_LIMIT,
+};
+use rmrk_traits::{
+    AccountIdOrCollectionNftTuple, BasicResource, CollectionId, ComposableResource,
+    EquippableList, FixedPart, PartId, PartType, ResourceTypes, SlotPart, SlotResource, Theme,
+    ThemeProperty,
+};
+use sp_runtime::{DispatchError, Permill};
+use sp_std::prelude::*;
+
+/// String bound of `pallet_rmrk_core`, inherited from `pallet_uniques`.
+type RmrkString = BoundedVec<u8, UniquesStringLimit>;
+type RmrkParts = BoundedVec<PartId, PartsLimit>;
+type RmrkResource = ResourceTypes<RmrkString, RmrkParts>;
+/// A resource as sent by a contract, before its strings and parts are bounded.
+pub type UnboundedResource = ResourceTypes<Vec<u8>, Vec<PartId>>;
+type RmrkCollectionList = BoundedVec<CollectionId, MaxCollectionsEquippablePerPart>;
+type RmrkThemeProperties = BoundedVec<ThemeProperty<RmrkString>, MaxPropertiesPerTheme>;
+
+/// `(owner, collection_id, royalty_recipient, royalty, metadata, transferable, resources)`
+type MintNftInput = (
+    AccountId,
+    u32,
+    Option<AccountId>,
+    Option<Permill>,
+    Vec<u8>,
+    bool,
+    Option<Vec<UnboundedResource>>,
+);
+/// `(collection_id, nft_id, priorities)`
+type SetPriorityInput = (u32, u32, Vec<u32>);
+/// `(base_type, symbol, parts)`
+type CreateBaseInput = (Vec<u8>, Vec<u8>, Vec<PartType<Vec<u8>, Vec<CollectionId>>>);
+/// `(base_id, theme)`
+type ThemeAddInput = (u32, Theme<Vec<u8>, Vec<ThemeProperty<Vec<u8>>>>);
+
+/// The contract itself is the issuer and owner on the RMRK side, unless the function
+/// explicitly acts for its caller, which only contracts in `CallerSigningContracts` may.
+pub struct RmrkExtension;
+
+impl RuntimeExtension for RmrkExtension {
+    fn owns(func_id: u32) -> bool {
+        func_id / 100 == RMRK_EXTENSION / 100
+    }
+
+    fn weight(func_id: u32, input: &[u8]) -> Weight {
+        let func = match RmrkFunc::try_from(func_id) {
+            Ok(func) => func,
+            Err(_) => return 0,
+        };
+        match func {
+            RmrkFunc::NextNftId => ExtensionWeight::next_nft_id(),
+            RmrkFunc::CollectionIndex => ExtensionWeight::collection_index(),
+            RmrkFunc::CollectionInfo => ExtensionWeight::collection_info(),
+            RmrkFunc::NftInfo => ExtensionWeight::nft_info(),
+            // Charged for a full page, a smaller `limit` does not bound the reads of a
+            // page in the runtime.
+            RmrkFunc::Children => ExtensionWeight::children(RMRK_PAGE_LIMIT),
+            RmrkFunc::Properties => ExtensionWeight::properties(RMRK_PAGE_LIMIT),
+            RmrkFunc::BalanceOf => ExtensionWeight::balance_of(RMRK_BALANCE_LIMIT),
+            RmrkFunc::MintNft => ExtensionWeight::mint_nft(Self::items(
+                input,
+                |(.., resources): MintNftInput| resources.map_or(0, |resources| resources.len()),
+            )),
+            RmrkFunc::CreateCollection => ExtensionWeight::create_collection(),
+            RmrkFunc::BurnNft => ExtensionWeight::burn_nft(),
+            RmrkFunc::Send | RmrkFunc::SendFromCaller => ExtensionWeight::send(),
+            RmrkFunc::AcceptNft => ExtensionWeight::accept_nft(),
+            RmrkFunc::RejectNft => ExtensionWeight::reject_nft(),
+            RmrkFunc::ChangeCollectionIssuer => ExtensionWeight::change_collection_issuer(),
+            RmrkFunc::AddBasicResource => ExtensionWeight::add_basic_resource(),
+            RmrkFunc::AddComposableResource => ExtensionWeight::add_composable_resource(),
+            RmrkFunc::AddSlotResource => ExtensionWeight::add_slot_resource(),
+            RmrkFunc::AcceptResource => ExtensionWeight::accept_resource(),
+            RmrkFunc::RemoveResource => ExtensionWeight::remove_resource(),
+            RmrkFunc::AcceptResourceRemoval => ExtensionWeight::accept_resource_removal(),
+            RmrkFunc::SetPriority => ExtensionWeight::set_priority(Self::items(
+                input,
+                |(.., priorities): SetPriorityInput| priorities.len(),
+            )),
+            RmrkFunc::CreateBase => ExtensionWeight::create_base(Self::items(
+                input,
+                |(.., parts): CreateBaseInput| parts.len(),
+            )),
+            RmrkFunc::ThemeAdd => ExtensionWeight::theme_add(Self::items(
+                input,
+                |(_, theme): ThemeAddInput| theme.properties.len(),
+            )),
+            RmrkFunc::Equip => ExtensionWeight::equip(),
+            RmrkFunc::Unequip => ExtensionWeight::unequip(),
+            RmrkFunc::Equippable => ExtensionWeight::equippable(),
+            RmrkFunc::ChangeBaseIssuer => ExtensionWeight::change_base_issuer(),
+        }
+    }
+
+    /// The issuer actions, which create collections and bases or change what they issue,
+    /// and sending for the caller.
+    fn is_privileged(func_id: u32) -> bool {
+        matches!(
+            RmrkFunc::try_from(func_id),
+            Ok(RmrkFunc::MintNft
+                | RmrkFunc::SendFromCaller
+                | RmrkFunc::CreateCollection
+                | RmrkFunc::ChangeCollectionIssuer
+                | RmrkFunc::AddBasicResource
+                | RmrkFunc::AddComposableResource
+                | RmrkFunc::AddSlotResource
+                | RmrkFunc::RemoveResource
+                | RmrkFunc::CreateBase
+                | RmrkFunc::ThemeAdd
+                | RmrkFunc::Equippable
+                | RmrkFunc::ChangeBaseIssuer)
+        )
+    }
+
+    fn dispatch(
+        func_id: u32,
+        input: &[u8],
+        origins: &Origins,
+    ) -> Result<CallOutcome, DispatchError> {
+        let func = RmrkFunc::try_from(func_id).map_err(unknown_func_id)?;
+        let signer = match func {
+            RmrkFunc::SendFromCaller => origins.caller_signer()?,
+            _ => origins.address.clone(),
+        };
+        let outcome = Self::call(func, input, Origin::signed(signer))?;
+        Ok(outcome.map_err(u32::from))
+    }
+}
+
+impl RmrkExtension {
+    fn call(
+        func: RmrkFunc,
+        mut input: &[u8],
+        origin: Origin,
+    ) -> Result<Result<Vec<u8>, RmrkStatusCode>, DispatchError> {
+        match func {
+            RmrkFunc::NextNftId => {
+                let collection_id: u32 = decode_input(&mut input)?;
+                let nft_id = RmrkCore::next_nft_id(collection_id);
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                Ok(Ok(nft_id.encode()))
+            }
+
+            RmrkFunc::CollectionIndex => {
+                let collection_index = RmrkCore::collection_index();
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_index:{:?}",
+                    func,
+                    collection_index
+                );
+                Ok(Ok(collection_index.encode()))
+            }
+
+            RmrkFunc::CollectionInfo => {
+                let collection_id: u32 = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}",
+                    func,
+                    collection_id
+                );
+                Ok(Ok(RmrkCore::collections(collection_id).encode()))
+            }
+
+            RmrkFunc::NftInfo => {
+                let (collection_id, nft_id): (u32, u32) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                Ok(Ok(RmrkCore::nfts(collection_id, nft_id).encode()))
+            }
+
+            RmrkFunc::Children => {
+                let (collection_id, nft_id, start, limit): (u32, u32, Option<(u32, u32)>, u32) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, start:{:?}, limit:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    start,
+                    limit
+                );
+                type Children = pallet_rmrk_core::Children<Runtime>;
+                let parent = (collection_id, nft_id);
+                let limit = limit.min(RMRK_PAGE_LIMIT) as usize;
+                let children: Vec<(u32, u32)> = match start {
+                    Some(start) => {
+                        let start = Children::hashed_key_for(parent, start);
+                        Children::iter_key_prefix_from(parent, start).take(limit).collect()
+                    }
+                    None => Children::iter_key_prefix(parent).take(limit).collect(),
+                };
+                Ok(Ok(children.encode()))
+            }
+
+            RmrkFunc::Properties => {
+                let (collection_id, nft_id, start, limit): (
+                    u32,
+                    Option<u32>,
+                    Option<Vec<u8>>,
+                    u32,
+                ) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, limit:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    limit
+                );
+                type Properties = pallet_rmrk_core::Properties<Runtime>;
+                let prefix = (collection_id, nft_id);
+                let limit = limit.min(RMRK_PAGE_LIMIT) as usize;
+                // Bounded keys and values encode like the contract's plain byte vectors.
+                let properties: Vec<_> = match start.map(Self::bounded::<u8, KeyLimit>) {
+                    Some(Ok(start)) => {
+                        let start = Properties::hashed_key_for((collection_id, nft_id, start));
+                        Properties::iter_prefix_from(prefix, start).take(limit).collect()
+                    }
+                    // No key follows one longer than any stored key.
+                    Some(Err(_)) => Vec::new(),
+                    None => Properties::iter_prefix(prefix).take(limit).collect(),
+                };
+                Ok(Ok(properties.encode()))
+            }
+
+            RmrkFunc::BalanceOf => {
+                let (owner, collection_id, start): (AccountId, u32, Option<u32>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} owner:{:?}, collection_id:{:?}, start:{:?}",
+                    func,
+                    owner,
+                    collection_id,
+                    start
+                );
+                // Nested NFTs are owned by their parent, so only direct holdings count.
+                type Account = pallet_uniques::Account<Runtime>;
+                let prefix = (owner.clone(), collection_id);
+                let limit = RMRK_BALANCE_LIMIT as usize;
+                let page: Vec<u32> = match start {
+                    Some(start) => {
+                        let start = Account::hashed_key_for((owner, collection_id, start));
+                        Account::iter_key_prefix_from(prefix, start).take(limit).collect()
+                    }
+                    None => Account::iter_key_prefix(prefix).take(limit).collect(),
+                };
+                let last = if page.len() == limit { page.last().copied() } else { None };
+                Ok(Ok((page.len() as u32, last).encode()))
+            }
+
+            RmrkFunc::MintNft => {
+                let (
+                    owner,
+                    collection_id,
+                    royalty_recipient,
+                    royalty,
+                    metadata,
+                    transferable,
+                    resources,
+                ): MintNftInput = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} owner:{:?}, collection_id:{:?}",
+                    func,
+                    owner,
+                    collection_id
+                );
+                // `Permill` decodes any `u32`, the pallet expects at most one whole.
+                if royalty.map_or(false, |royalty| royalty > Permill::one()) {
+                    return Ok(Err(RmrkStatusCode::RoyaltyOutOfRange));
+                }
+                Ok(Self::bounded(metadata).and_then(|metadata| {
+                    let resources = resources.map(Self::bounded_resources).transpose()?;
+                    // The pallet mints with the next ID, reading it within this call is atomic.
+                    let nft_id = RmrkCore::next_nft_id(collection_id);
+                    let result = RmrkCore::mint_nft(
+                        origin,
+                        owner,
+                        collection_id,
+                        royalty_recipient,
+                        royalty,
+                        metadata,
+                        transferable,
+                        resources,
+                    );
+                    Self::rmrk_status_of(result).map(|()| (collection_id, nft_id).encode())
+                }))
+            }
+
+            RmrkFunc::CreateCollection => {
+                let (metadata, max, symbol): (Vec<u8>, Option<u32>, Vec<u8>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} max:{:?}",
+                    func,
+                    max
+                );
+                Ok(Self::bounded(metadata).and_then(|metadata| {
+                    let symbol = Self::bounded(symbol)?;
+                    let collection_id = RmrkCore::collection_index();
+                    let result = RmrkCore::create_collection(origin, metadata, max, symbol);
+                    Self::rmrk_status_of(result).map(|()| collection_id.encode())
+                }))
+            }
+
+            RmrkFunc::BurnNft => {
+                let (collection_id, nft_id): (u32, u32) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                let result = RmrkCore::burn_nft(origin, collection_id, nft_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::Send | RmrkFunc::SendFromCaller => {
+                let (collection_id, nft_id, new_owner): (
+                    u32,
+                    u32,
+                    AccountIdOrCollectionNftTuple<AccountId>,
+                ) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, new_owner:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    new_owner
+                );
+                let result = RmrkCore::send(origin, collection_id, nft_id, new_owner);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::AcceptNft => {
+                let (collection_id, nft_id, new_owner): (
+                    u32,
+                    u32,
+                    AccountIdOrCollectionNftTuple<AccountId>,
+                ) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, new_owner:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    new_owner
+                );
+                let result = RmrkCore::accept_nft(origin, collection_id, nft_id, new_owner);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::RejectNft => {
+                let (collection_id, nft_id): (u32, u32) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                let result = RmrkCore::reject_nft(origin, collection_id, nft_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::ChangeCollectionIssuer => {
+                let (collection_id, new_issuer): (u32, AccountId) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, new_issuer:{:?}",
+                    func,
+                    collection_id,
+                    new_issuer
+                );
+                let result =
+                    RmrkCore::change_collection_issuer(origin, collection_id, Address::Id(new_issuer));
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::AddBasicResource => {
+                let (collection_id, nft_id, resource): (u32, u32, BasicResource<Vec<u8>>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                Ok(Self::bounded_basic(resource).and_then(|resource| {
+                    let result = RmrkCore::add_basic_resource(origin, collection_id, nft_id, resource);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::AddComposableResource => {
+                let (collection_id, nft_id, resource): (
+                    u32,
+                    u32,
+                    ComposableResource<Vec<u8>, Vec<PartId>>,
+                ) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                Ok(Self::bounded_composable(resource).and_then(|resource| {
+                    let result =
+                        RmrkCore::add_composable_resource(origin, collection_id, nft_id, resource);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::AddSlotResource => {
+                let (collection_id, nft_id, resource): (u32, u32, SlotResource<Vec<u8>>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id
+                );
+                Ok(Self::bounded_slot(resource).and_then(|resource| {
+                    let result = RmrkCore::add_slot_resource(origin, collection_id, nft_id, resource);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::AcceptResource => {
+                let (collection_id, nft_id, resource_id): (u32, u32, u32) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, resource_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    resource_id
+                );
+                let result = RmrkCore::accept_resource(origin, collection_id, nft_id, resource_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::RemoveResource => {
+                let (collection_id, nft_id, resource_id): (u32, u32, u32) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, resource_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    resource_id
+                );
+                let result = RmrkCore::remove_resource(origin, collection_id, nft_id, resource_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::AcceptResourceRemoval => {
+                let (collection_id, nft_id, resource_id): (u32, u32, u32) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, resource_id:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    resource_id
+                );
+                let result =
+                    RmrkCore::accept_resource_removal(origin, collection_id, nft_id, resource_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::SetPriority => {
+                let (collection_id, nft_id, priorities): SetPriorityInput =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} collection_id:{:?}, nft_id:{:?}, priorities:{:?}",
+                    func,
+                    collection_id,
+                    nft_id,
+                    priorities
+                );
+                Ok(Self::bounded(priorities).and_then(|priorities| {
+                    let result = RmrkCore::set_priority(origin, collection_id, nft_id, priorities);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::CreateBase => {
+                let (base_type, symbol, parts): CreateBaseInput = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} parts:{:?}",
+                    func,
+                    parts.len()
+                );
+                Ok(Self::bounded(base_type).and_then(|base_type| {
+                    let symbol = Self::bounded(symbol)?;
+                    let parts = parts
+                        .into_iter()
+                        .map(Self::bounded_part)
+                        .collect::<Result<Vec<_>, RmrkStatusCode>>()?;
+                    let result =
+                        RmrkEquip::create_base(origin, base_type, symbol, Self::bounded(parts)?);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::ThemeAdd => {
+                let (base_id, theme): ThemeAddInput = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} base_id:{:?}",
+                    func,
+                    base_id
+                );
+                Ok(Self::bounded_theme(theme).and_then(|theme| {
+                    let result = RmrkEquip::theme_add(origin, base_id, theme);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::Equip => {
+                let (item, equipper, resource_id, base_id, slot_id): (
+                    (u32, u32),
+                    (u32, u32),
+                    u32,
+                    u32,
+                    u32,
+                ) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} item:{:?}, equipper:{:?}, slot_id:{:?}",
+                    func,
+                    item,
+                    equipper,
+                    slot_id
+                );
+                let result =
+                    RmrkEquip::equip(origin, item, equipper, resource_id, base_id, slot_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::Unequip => {
+                let (item, unequipper, base_id, slot_id): ((u32, u32), (u32, u32), u32, u32) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} item:{:?}, unequipper:{:?}, slot_id:{:?}",
+                    func,
+                    item,
+                    unequipper,
+                    slot_id
+                );
+                let result = RmrkEquip::unequip(origin, item, unequipper, base_id, slot_id);
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+
+            RmrkFunc::Equippable => {
+                let (base_id, slot_id, equippables): (u32, u32, EquippableList<Vec<CollectionId>>) =
+                    decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} base_id:{:?}, slot_id:{:?}",
+                    func,
+                    base_id,
+                    slot_id
+                );
+                Ok(Self::bounded_equippable(equippables).and_then(|equippables| {
+                    let result = RmrkEquip::equippable(origin, base_id, slot_id, equippables);
+                    Self::rmrk_status_of(result).map(|()| Vec::new())
+                }))
+            }
+
+            RmrkFunc::ChangeBaseIssuer => {
+                let (base_id, new_issuer): (u32, AccountId) = decode_input(&mut input)?;
+                trace!(
+                    target: "runtime",
+                    "[ChainExtension]|call|func:{:?} base_id:{:?}, new_issuer:{:?}",
+                    func,
+                    base_id,
+                    new_issuer
+                );
+                let result = RmrkEquip::change_base_issuer(origin, base_id, Address::Id(new_issuer));
+                Ok(Self::rmrk_status_of(result).map(|()| Vec::new()))
+            }
+        }
+    }
+
+    /// Number of items `len` counts in the decoded `input`, zero if it does not decode, as
+    /// `dispatch` then traps before writing anything.
+    fn items<T: Decode>(mut input: &[u8], len: impl FnOnce(T) -> usize) -> u32 {
+        T::decode(&mut input).map_or(0, |decoded| len(decoded) as u32)
+    }
+
+    /// Converts a contract supplied vector into the bound expected by `pallet_rmrk_core`.
+    fn bounded<T, S: Get<u32>>(items: Vec<T>) -> Result<BoundedVec<T, S>, RmrkStatusCode> {
+        items.try_into().map_err(|_| RmrkStatusCode::TooLong)
+    }
+
+    fn bounded_string(bytes: Option<Vec<u8>>) -> Result<Option<RmrkString>, RmrkStatusCode> {
+        bytes.map(Self::bounded).transpose()
+    }
+
+    fn bounded_basic(
+        resource: BasicResource<Vec<u8>>,
+    ) -> Result<BasicResource<RmrkString>, RmrkStatusCode> {
+        Ok(BasicResource {
+            src: Self::bounded_string(resource.src)?,
+            metadata: Self::bounded_string(resource.metadata)?,
+            license: Self::bounded_string(resource.license)?,
+            thumb: Self::bounded_string(resource.thumb)?,
+        })
+    }
+
+    fn bounded_composable(
+        resource: ComposableResource<Vec<u8>, Vec<PartId>>,
+    ) -> Result<ComposableResource<RmrkString, RmrkParts>, RmrkStatusCode> {
+        Ok(ComposableResource {
+            parts: Self::bounded(resource.parts)?,
+            base: resource.base,
+            src: Self::bounded_string(resource.src)?,
+            metadata: Self::bounded_string(resource.metadata)?,
+            license: Self::bounded_string(resource.license)?,
+            thumb: Self::bounded_string(resource.thumb)?,
+        })
+    }
+
+    fn bounded_slot(
+        resource: SlotResource<Vec<u8>>,
+    ) -> Result<SlotResource<RmrkString>, RmrkStatusCode> {
+        Ok(SlotResource {
+            base: resource.base,
+            src: Self::bounded_string(resource.src)?,
+            metadata: Self::bounded_string(resource.metadata)?,
+            slot: resource.slot,
+            license: Self::bounded_string(resource.license)?,
+            thumb: Self::bounded_string(resource.thumb)?,
+        })
+    }
+
+    fn bounded_equippable(
+        equippables: EquippableList<Vec<CollectionId>>,
+    ) -> Result<EquippableList<RmrkCollectionList>, RmrkStatusCode> {
+        Ok(match equippables {
+            EquippableList::All => EquippableList::All,
+            EquippableList::Empty => EquippableList::Empty,
+            EquippableList::Custom(collections) => EquippableList::Custom(Self::bounded(collections)?),
+        })
+    }
+
+    fn bounded_part(
+        part: PartType<Vec<u8>, Vec<CollectionId>>,
+    ) -> Result<PartType<RmrkString, RmrkCollectionList>, RmrkStatusCode> {
+        Ok(match part {
+            PartType::FixedPart(part) => PartType::FixedPart(FixedPart {
+                id: part.id,
+                z: part.z,
+                src: Self::bounded(part.src)?,
+            }),
+            PartType::SlotPart(part) => PartType::SlotPart(SlotPart {
+                id: part.id,
+                equippable: Self::bounded_equippable(part.equippable)?,
+                src: Self::bounded(part.src)?,
+                z: part.z,
+            }),
+        })
+    }
+
+    fn bounded_theme(
+        theme: Theme<Vec<u8>, Vec<ThemeProperty<Vec<u8>>>>,
+    ) -> Result<Theme<RmrkString, RmrkThemeProperties>, RmrkStatusCode> {
+        let properties = theme
+            .properties
+            .into_iter()
+            .map(|property| {
+                Ok(ThemeProperty {
+                    key: Self::bounded(property.key)?,
+                    value: Self::bounded(property.value)?,
+                })
+            })
+            .collect::<Result<Vec<_>, RmrkStatusCode>>()?;
+        Ok(Theme {
+            name: Self::bounded(theme.name)?,
+            properties: Self::bounded(properties)?,
+            inherit: theme.inherit,
+        })
+    }
+
+    fn bounded_resources(
+        resources: Vec<UnboundedResource>,
+    ) -> Result<BoundedVec<RmrkResource, MaxResourcesOnMint>, RmrkStatusCode> {
+        let resources = resources
+            .into_iter()
+            .map(|resource| {
+                Ok(match resource {
+                    ResourceTypes::Basic(r) => ResourceTypes::Basic(Self::bounded_basic(r)?),
+                    ResourceTypes::Composable(r) => {
+                        ResourceTypes::Composable(Self::bounded_composable(r)?)
+                    }
+                    ResourceTypes::Slot(r) => ResourceTypes::Slot(Self::bounded_slot(r)?),
+                })
+            })
+            .collect::<Result<Vec<_>, RmrkStatusCode>>()?;
+        Self::bounded(resources)
+    }
+
+    /// Maps a failed `pallet_rmrk_core` or `pallet_rmrk_equip` dispatch to the
+    /// `RmrkStatusCode` reported to the contract.
+    fn rmrk_status_of(result: DispatchResult) -> Result<(), RmrkStatusCode> {
+        use pallet_rmrk_core::Error;
+        use pallet_rmrk_equip::Error as EquipError;
+
+        result.map_err(|error| {
+            let is = |expected: Error<Runtime>| error == expected.into();
+            let is_equip = |expected: EquipError<Runtime>| error == expected.into();
+            if is(Error::CollectionUnknown) {
+                RmrkStatusCode::CollectionUnknown
+            } else if is(Error::NoPermission) {
+                RmrkStatusCode::NoPermission
+            } else if is(Error::CollectionFullOrLocked) {
+                RmrkStatusCode::CollectionFullOrLocked
+            } else if is(Error::NonTransferable) {
+                RmrkStatusCode::NonTransferable
+            } else if is(Error::TooLong) {
+                RmrkStatusCode::TooLong
+            } else if is(Error::NoAvailableNftId) {
+                RmrkStatusCode::NftUnknown
+            } else if is(Error::NftIsLocked) {
+                RmrkStatusCode::NftLocked
+            } else if is(Error::CannotSendToDescendentOrSelf) {
+                RmrkStatusCode::InvalidDestination
+            } else if is(Error::CannotAcceptNonOwnedNft) || is(Error::CannotRejectNonOwnedNft) {
+                RmrkStatusCode::NoPermission
+            } else if is(Error::ResourceDoesntExist) {
+                RmrkStatusCode::ResourceUnknown
+            } else if is(Error::ResourceNotPending) {
+                RmrkStatusCode::ResourceNotPending
+            } else if is_equip(EquipError::PermissionError) {
+                RmrkStatusCode::NoPermission
+            } else if is_equip(EquipError::ItemDoesntExist)
+                || is_equip(EquipError::EquipperDoesntExist)
+            {
+                RmrkStatusCode::NftUnknown
+            } else if is_equip(EquipError::BaseDoesntExist) {
+                RmrkStatusCode::BaseUnknown
+            } else if is_equip(EquipError::PartDoesntExist) {
+                RmrkStatusCode::PartUnknown
+            } else if is_equip(EquipError::MustBeDirectParent)
+                || is_equip(EquipError::CantEquipFixedPart)
+                || is_equip(EquipError::NoEquippableOnFixedPart)
+                || is_equip(EquipError::CollectionNotEquippable)
+                || is_equip(EquipError::NoResourceForThisBaseFoundOnNft)
+                || is_equip(EquipError::ItemHasNoResourceToEquipThere)
+            {
+                RmrkStatusCode::NotEquippable
+            } else if is_equip(EquipError::AlreadyEquipped) {
+                RmrkStatusCode::AlreadyEquipped
+            } else if is_equip(EquipError::ItemNotEquipped) {
+                RmrkStatusCode::NotEquipped
+            } else if is_equip(EquipError::NeedsDefaultThemeFirst) {
+                RmrkStatusCode::NeedsDefaultTheme
+            } else if is_equip(EquipError::ExceedsMaxPartsPerBase)
+                || is_equip(EquipError::TooManyProperties)
+            {
+                RmrkStatusCode::TooLong
+            } else {
+                RmrkStatusCode::Failed
+            }
+        })
+    }
+}
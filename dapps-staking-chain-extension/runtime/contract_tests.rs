@@ -0,0 +1,1026 @@
+//! The example contracts, compiled to Wasm, calling `LocalChainExtension` via `pallet_contracts`.
+//!
+//! The tests upload the blobs from the `target/ink` directory of each contract, build them
+//! first with `cargo contract build` in `dapps-staking-chain-extension`, `rmrk_chain_test`,
+//! `rmrk-psp34`, `psp22-assets` and `randomness-chain-extension`.
+//!
+//! The values the contracts return are decoded with the types of the contract crates, which
+//! the runtime lists under `[dev-dependencies]`. Run `cargo test` in `runtime` once the blobs
+//! are built.
+
+use super::*;
+use crate::chain_extensions::UnboundedResource;
+use crate::tests::{account, new_test_ext};
+use codec::Compact;
+use dapps_staking_chain_extension_types::StakingOrigin;
+use ds::{DSError, DSErrorCode};
+use frame_support::{
+    assert_ok,
+    traits::{OnInitialize, OnTimestampSet, Randomness},
+};
+use pallet_contracts_primitives::Code;
+use psp22_assets::PSP22Error;
+use randomness_extension::{RandomnessError, REVEAL_WINDOW};
+use rmrk_chain_extension_types::RmrkFunc;
+use rmrk_chain_test::{RmrkError, RmrkErrorCode};
+use rmrk_psp34::{Id, PSP34Error};
+use rmrk_traits::{
+    AccountIdOrCollectionNftTuple, BasicResource, CollectionId, ComposableResource,
+    EquippableList, FixedPart, PartType, SlotPart, SlotResource, Theme, ThemeProperty,
+};
+
+const DS_WASM: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../target/ink/ds.wasm");
+const RMRK_WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../rmrk_chain_test/target/ink/rmrk_chain_test.wasm"
+);
+const PSP34_WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../rmrk-psp34/target/ink/rmrk_psp34.wasm"
+);
+const PSP22_WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../psp22-assets/target/ink/psp22_assets.wasm"
+);
+const RANDOMNESS_WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../randomness-chain-extension/target/ink/randomness_extension.wasm"
+);
+
+const GAS_LIMIT: Weight = 10 * WEIGHT_PER_SECOND;
+
+/// Blocks mixed into a seed of `pallet_randomness_collective_flip`, the seed is known since
+/// that many blocks back. The pallet keeps its constant private.
+const RANDOM_MATERIAL_LEN: BlockNumber = 81;
+
+fn wasm(path: &str) -> Vec<u8> {
+    std::fs::read(path)
+        .unwrap_or_else(|_| panic!("{} is missing, build it with `cargo contract build`", path))
+}
+
+/// Selector of an ink! message or constructor, `Trait::message` for trait messages.
+fn selector(name: &str) -> [u8; 4] {
+    let hash = sp_core::blake2_256(name.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Input of the message `name`, the arguments are encoded one after the other like a tuple.
+fn message(name: &str, args: impl Encode) -> Vec<u8> {
+    let mut input = selector(name).to_vec();
+    args.encode_to(&mut input);
+    input
+}
+
+/// Uploads and instantiates `code` as `origin`, the new contract is given 1000 AST.
+fn instantiate(origin: &AccountId, code: Vec<u8>, constructor: Vec<u8>) -> AccountId {
+    let result = Contracts::bare_instantiate(
+        origin.clone(),
+        0,
+        GAS_LIMIT,
+        None,
+        Code::Upload(code.into()),
+        constructor,
+        Vec::new(),
+        true,
+    );
+    let contract = result
+        .result
+        .unwrap_or_else(|error| {
+            panic!(
+                "instantiation failed with {:?}: {}",
+                error,
+                String::from_utf8_lossy(&result.debug_message)
+            )
+        })
+        .account_id;
+    // Registering for staking, creating collections and bases take a deposit from the contract.
+    Balances::make_free_balance_be(&contract, 1_000 * AST);
+    contract
+}
+
+/// Calls `contract` as `origin` and returns the encoded value it returned.
+fn call_raw(origin: &AccountId, contract: &AccountId, input: Vec<u8>) -> Vec<u8> {
+    let result = Contracts::bare_call(
+        origin.clone(),
+        contract.clone(),
+        0,
+        GAS_LIMIT,
+        None,
+        input,
+        true,
+    );
+    let output = result.result.unwrap_or_else(|error| {
+        panic!(
+            "call trapped with {:?}: {}",
+            error,
+            String::from_utf8_lossy(&result.debug_message)
+        )
+    });
+    output.data.0
+}
+
+/// Calls `contract` as `origin` and decodes the returned value.
+fn call<R: Decode>(origin: &AccountId, contract: &AccountId, input: Vec<u8>) -> R {
+    R::decode(&mut &call_raw(origin, contract, input)[..])
+        .expect("output decodes to the expected type")
+}
+
+/// Lets every instance of the code of `contract` sign with the account calling it,
+/// see `CallerSigningContracts`.
+fn allow_caller_signing(contract: &AccountId) {
+    let code_hash = pallet_contracts::ContractInfoOf::<Runtime>::get(contract)
+        .expect("contract is instantiated")
+        .code_hash;
+    let mut allowed = CallerSigningContracts::get();
+    allowed.push(AllowedContract::CodeHash(code_hash));
+    CallerSigningContracts::set(&allowed);
+}
+
+/// Imports every block up to the start of `era`, paying block rewards into dapps staking.
+fn run_to_era(era: u32) {
+    while DappsStaking::current_era() < era {
+        let block_number = System::block_number() + 1;
+        System::set_block_number(block_number);
+        DappsStaking::on_initialize(block_number);
+        <BlockReward as OnTimestampSet<u64>>::on_timestamp_set(0);
+    }
+}
+
+/// Funds `alice` and starts era 1, era 0 has no era info.
+fn setup(alice: &AccountId) {
+    Balances::make_free_balance_be(alice, 1_000_000 * AST);
+    run_to_era(1);
+}
+
+#[test]
+fn dapps_staking_messages_reach_the_pallet() {
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let ds = instantiate(&alice, wasm(DS_WASM), message("new", ()));
+        let contract_id = SmartContract::Wasm(ds.clone());
+        let stake = 2 * MinimumStakingAmount::get();
+
+        assert_eq!(
+            call::<Result<u32, DSError>>(&alice, &ds, message("read_current_era", ())),
+            Ok(1)
+        );
+        assert_eq!(
+            call::<Result<pallet_dapps_staking::EraInfo<Balance>, DSError>>(
+                &alice,
+                &ds,
+                message("read_era_info", 1u32)
+            ),
+            Ok(DappsStaking::general_era_info(1).expect("recorded on era change"))
+        );
+        assert_eq!(
+            call::<Result<pallet_dapps_staking::EraInfo<Balance>, DSError>>(
+                &alice,
+                &ds,
+                message("read_era_info", 100u32)
+            ),
+            Err(DSError::ErrorCode(DSErrorCode::EraNotFound))
+        );
+
+        let bond_and_stake =
+            message("bond_and_stake", (StakingOrigin::Caller, contract_id.clone(), stake));
+        // Signing with its caller traps until the contract is allowed to.
+        let result = Contracts::bare_call(
+            alice.clone(),
+            ds.clone(),
+            0,
+            GAS_LIMIT,
+            None,
+            bond_and_stake.clone(),
+            true,
+        )
+        .result;
+        assert_eq!(
+            result.map(|_| ()),
+            Err(DispatchError::Other("ChainExtension caller origin not allowed"))
+        );
+        allow_caller_signing(&ds);
+        assert_eq!(
+            call::<Result<(), DSError>>(&alice, &ds, bond_and_stake.clone()),
+            Err(DSError::ErrorCode(DSErrorCode::NotRegistered))
+        );
+        assert_eq!(
+            call::<Result<(), DSError>>(&alice, &ds, message("register", ())),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Result<(), DSError>>(&alice, &ds, message("register", ())),
+            Err(DSError::ErrorCode(DSErrorCode::AlreadyRegistered))
+        );
+
+        // Staked by the caller of the contract, not the contract.
+        assert_eq!(
+            call::<Result<(), DSError>>(&alice, &ds, bond_and_stake),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Result<Balance, DSError>>(
+                &alice,
+                &ds,
+                message("read_staked_amount", (alice.clone(), contract_id.clone()))
+            ),
+            Ok(stake)
+        );
+        // Staked by the contract, with its own funds.
+        assert_eq!(
+            call::<Result<(), DSError>>(
+                &alice,
+                &ds,
+                message("bond_and_stake", (StakingOrigin::Address, contract_id.clone(), stake))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Result<Balance, DSError>>(
+                &alice,
+                &ds,
+                message("read_staked_amount", (ds.clone(), contract_id.clone()))
+            ),
+            Ok(stake)
+        );
+        assert_eq!(
+            call::<Result<Option<pallet_dapps_staking::ContractStakeInfo<Balance>>, DSError>>(
+                &alice,
+                &ds,
+                message("read_contract_stake", (contract_id.clone(), 1u32))
+            ),
+            Ok(Some(
+                DappsStaking::contract_stake_info(&contract_id, 1).expect("staked in era 1")
+            ))
+        );
+
+        // Amount of the `Reward` event of era 1 naming `who` and the staked contract.
+        let reward_event = |who: &AccountId| {
+            System::events()
+                .iter()
+                .find_map(|record| match &record.event {
+                    Event::DappsStaking(pallet_dapps_staking::Event::Reward(
+                        account,
+                        contract,
+                        1,
+                        amount,
+                    )) if account == who && *contract == contract_id => Some(*amount),
+                    _ => None,
+                })
+                .expect("claim deposited a Reward event")
+        };
+
+        run_to_era(2);
+        let claimed = call::<Result<Balance, DSError>>(
+            &alice,
+            &ds,
+            message("claim_staker", contract_id.clone()),
+        );
+        assert!(matches!(claimed, Ok(reward) if reward > 0), "{:?}", claimed);
+        assert_eq!(claimed, Ok(reward_event(&alice)));
+        let claimed = call::<Result<Balance, DSError>>(
+            &alice,
+            &ds,
+            message("claim_dapp", (contract_id.clone(), 1u32)),
+        );
+        assert!(matches!(claimed, Ok(reward) if reward > 0), "{:?}", claimed);
+        // The contract was registered by the contract itself, its developer.
+        assert_eq!(claimed, Ok(reward_event(&ds)));
+
+        let unbond = MinimumStakingAmount::get();
+        assert_eq!(
+            call::<Result<(), DSError>>(
+                &alice,
+                &ds,
+                message(
+                    "unbond_and_unstake",
+                    (StakingOrigin::Caller, contract_id.clone(), unbond)
+                )
+            ),
+            Ok(())
+        );
+        // Each `UnlockingChunk` is a compact amount and a compact unlock era.
+        let unlock_era = 2 + UnbondingPeriod::get();
+        assert_eq!(
+            call::<Result<Vec<(Compact<Balance>, Compact<u32>)>, DSError>>(
+                &alice,
+                &ds,
+                message("read_unbonding_info", alice.clone())
+            ),
+            Ok(vec![(Compact(unbond), Compact(unlock_era))])
+        );
+        let withdraw = message("withdraw_unbonded", StakingOrigin::Caller);
+        assert_eq!(
+            call::<Result<(), DSError>>(&alice, &ds, withdraw.clone()),
+            Err(DSError::ErrorCode(DSErrorCode::NothingToWithdraw))
+        );
+        run_to_era(unlock_era);
+        assert_eq!(call::<Result<(), DSError>>(&alice, &ds, withdraw), Ok(()));
+        assert_eq!(
+            call::<Result<Vec<(Compact<Balance>, Compact<u32>)>, DSError>>(
+                &alice,
+                &ds,
+                message("read_unbonding_info", alice.clone())
+            ),
+            Ok(Vec::new())
+        );
+    });
+}
+
+#[test]
+fn rmrk_nft_messages_reach_the_pallet() {
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let rmrk = instantiate(&alice, wasm(RMRK_WASM), message("new", ()));
+        let mint = |beneficiary: &AccountId| {
+            message(
+                "mint_ntf",
+                (
+                    beneficiary.clone(),
+                    0u32,
+                    None::<AccountId>,
+                    None::<Permill>,
+                    b"ipfs://nft".to_vec(),
+                    true,
+                    None::<Vec<UnboundedResource>>,
+                ),
+            )
+        };
+
+        assert_eq!(
+            call::<Result<u32, RmrkError>>(&alice, &rmrk, message("collection_index", ())),
+            Ok(0)
+        );
+        assert_eq!(
+            call::<Result<u32, RmrkError>>(
+                &alice,
+                &rmrk,
+                message(
+                    "create_collection",
+                    (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec())
+                )
+            ),
+            Ok(0)
+        );
+        assert_eq!(
+            call::<Result<u32, RmrkError>>(&alice, &rmrk, message("next_nft_id", 0u32)),
+            Ok(0)
+        );
+        for nft_id in 0..3u32 {
+            assert_eq!(
+                call::<Result<(u32, u32), RmrkError>>(&alice, &rmrk, mint(&rmrk)),
+                Ok((0, nft_id))
+            );
+        }
+        // The contract answers with the encoded storage of the pallet.
+        assert_eq!(
+            call_raw(&alice, &rmrk, message("collection", 0u32)),
+            Ok::<_, ()>(RmrkCore::collections(0)).encode()
+        );
+
+        let into_nft_0 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 0u32);
+        let into_nft_1 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 1u32);
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, message("send", (0u32, 1u32, into_nft_0))),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, message("send", (0u32, 0u32, into_nft_1))),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::InvalidDestination))
+        );
+        assert_eq!(
+            call::<Result<Vec<(u32, u32)>, RmrkError>>(
+                &alice,
+                &rmrk,
+                message("children", (0u32, 0u32, None::<(u32, u32)>, 10u32))
+            ),
+            Ok(vec![(0, 1)])
+        );
+        assert_eq!(
+            call::<Result<Vec<(Vec<u8>, Vec<u8>)>, RmrkError>>(
+                &alice,
+                &rmrk,
+                message("properties", (0u32, Some(0u32), None::<Vec<u8>>, 10u32))
+            ),
+            Ok(Vec::new())
+        );
+
+        // Alice sends NFTs of her own collection into NFT 2 of the contract, pending until
+        // the contract accepts or rejects them.
+        let as_alice = |func: RmrkFunc, input: Vec<u8>| {
+            LocalChainExtension::dispatch(func.into(), &input, None, alice.clone())
+        };
+        let create = (b"ipfs://alice".to_vec(), None::<u32>, b"ALI".to_vec());
+        assert_eq!(
+            as_alice(RmrkFunc::CreateCollection, create.encode()),
+            Ok(Ok(1u32.encode()))
+        );
+        let into_nft_2 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 2u32);
+        for nft_id in 0..2u32 {
+            let mint = (
+                alice.clone(),
+                1u32,
+                None::<AccountId>,
+                None::<Permill>,
+                b"ipfs://nft".to_vec(),
+                true,
+                None::<Vec<UnboundedResource>>,
+            );
+            assert_eq!(
+                as_alice(RmrkFunc::MintNft, mint.encode()),
+                Ok(Ok((1u32, nft_id).encode()))
+            );
+            assert_eq!(
+                as_alice(RmrkFunc::Send, (1u32, nft_id, into_nft_2.clone()).encode()),
+                Ok(Ok(Vec::new()))
+            );
+        }
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("accept_nft", (1u32, 0u32, into_nft_2))
+            ),
+            Ok(())
+        );
+        assert!(RmrkCore::nfts(1, 0).map_or(false, |nft| !nft.pending));
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, message("reject_nft", (1u32, 1u32))),
+            Ok(())
+        );
+        assert!(RmrkCore::nfts(1, 1).is_none());
+
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, message("burn_nft", (0u32, 7u32))),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::NftUnknown))
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, message("burn_nft", (0u32, 0u32))),
+            Ok(())
+        );
+        // Burning the parent burns the nested child as well.
+        assert!(RmrkCore::nfts(0, 1).is_none());
+
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("change_collection_issuer", (0u32, alice.clone()))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            RmrkCore::collections(0).map(|collection| collection.issuer),
+            Some(alice)
+        );
+    });
+}
+
+#[test]
+fn rmrk_resource_messages_reach_the_pallet() {
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let rmrk = instantiate(&alice, wasm(RMRK_WASM), message("new", ()));
+        let basic = |src: &[u8]| BasicResource {
+            src: Some(src.to_vec()),
+            metadata: None,
+            license: None,
+            thumb: None,
+        };
+
+        let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+        assert_eq!(
+            call::<Result<u32, RmrkError>>(&alice, &rmrk, message("create_collection", create)),
+            Ok(0)
+        );
+        let mint = (
+            rmrk.clone(),
+            0u32,
+            None::<AccountId>,
+            None::<Permill>,
+            b"ipfs://nft".to_vec(),
+            true,
+            Some(vec![UnboundedResource::Basic(basic(b"ipfs://0"))]),
+        );
+        assert_eq!(
+            call::<Result<(u32, u32), RmrkError>>(&alice, &rmrk, message("mint_ntf", mint)),
+            Ok((0, 0))
+        );
+
+        // The contract owns the NFT, so the resource is accepted right away.
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("add_basic_resource", (0u32, 0u32, basic(b"ipfs://1")))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("accept_resource", (0u32, 0u32, 1u32))
+            ),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::ResourceNotPending))
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("set_priority", (0u32, 0u32, vec![1u32, 0]))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("remove_resource", (0u32, 0u32, 9u32))
+            ),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::ResourceUnknown))
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("remove_resource", (0u32, 0u32, 1u32))
+            ),
+            Ok(())
+        );
+        assert!(!pallet_rmrk_core::Resources::<Runtime>::contains_key((
+            0, 0, 1
+        )));
+
+        // On an NFT of the contract in a collection of Alice, her resource and its removal
+        // stay pending until the contract accepts them.
+        let as_alice = |func: RmrkFunc, input: Vec<u8>| {
+            LocalChainExtension::dispatch(func.into(), &input, None, alice.clone())
+        };
+        let create = (b"ipfs://alice".to_vec(), None::<u32>, b"ALI".to_vec());
+        assert_eq!(
+            as_alice(RmrkFunc::CreateCollection, create.encode()),
+            Ok(Ok(1u32.encode()))
+        );
+        let mint = (
+            rmrk.clone(),
+            1u32,
+            None::<AccountId>,
+            None::<Permill>,
+            b"ipfs://nft".to_vec(),
+            true,
+            None::<Vec<UnboundedResource>>,
+        );
+        assert_eq!(
+            as_alice(RmrkFunc::MintNft, mint.encode()),
+            Ok(Ok((1u32, 0u32).encode()))
+        );
+        assert_eq!(
+            as_alice(
+                RmrkFunc::AddBasicResource,
+                (1u32, 0u32, basic(b"ipfs://alice")).encode()
+            ),
+            Ok(Ok(Vec::new()))
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("accept_resource", (1u32, 0u32, 0u32))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            as_alice(RmrkFunc::RemoveResource, (1u32, 0u32, 0u32).encode()),
+            Ok(Ok(Vec::new()))
+        );
+        assert!(pallet_rmrk_core::Resources::<Runtime>::contains_key((
+            1, 0, 0
+        )));
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("accept_resource_removal", (1u32, 0u32, 0u32))
+            ),
+            Ok(())
+        );
+        assert!(!pallet_rmrk_core::Resources::<Runtime>::contains_key((
+            1, 0, 0
+        )));
+    });
+}
+
+#[test]
+fn rmrk_equip_messages_reach_the_pallet() {
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let rmrk = instantiate(&alice, wasm(RMRK_WASM), message("new", ()));
+        let ok: Result<(), RmrkError> = Ok(());
+
+        let parts: Vec<PartType<Vec<u8>, Vec<CollectionId>>> = vec![
+            PartType::FixedPart(FixedPart {
+                id: 0,
+                z: 0,
+                src: b"body".to_vec(),
+            }),
+            PartType::SlotPart(SlotPart {
+                id: 1,
+                equippable: EquippableList::All,
+                src: b"hand".to_vec(),
+                z: 1,
+            }),
+        ];
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("create_base", (b"svg".to_vec(), b"KANARIA".to_vec(), parts))
+            ),
+            ok
+        );
+        let theme = |name: &[u8]| Theme {
+            name: name.to_vec(),
+            properties: vec![ThemeProperty {
+                key: b"primary".to_vec(),
+                value: b"red".to_vec(),
+            }],
+            inherit: false,
+        };
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("theme_add", (0u32, theme(b"dark")))
+            ),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::NeedsDefaultTheme))
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("theme_add", (0u32, theme(b"default")))
+            ),
+            ok
+        );
+
+        // NFT 0 is rendered with the base, NFT 1 fits into its slot part.
+        let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+        assert_eq!(
+            call::<Result<u32, RmrkError>>(&alice, &rmrk, message("create_collection", create)),
+            Ok(0)
+        );
+        for nft_id in 0..2u32 {
+            let mint = (
+                rmrk.clone(),
+                0u32,
+                None::<AccountId>,
+                None::<Permill>,
+                b"ipfs://nft".to_vec(),
+                true,
+                None::<Vec<UnboundedResource>>,
+            );
+            assert_eq!(
+                call::<Result<(u32, u32), RmrkError>>(&alice, &rmrk, message("mint_ntf", mint)),
+                Ok((0, nft_id))
+            );
+        }
+        let composable = ComposableResource {
+            parts: vec![0u32, 1],
+            base: 0u32,
+            src: None::<Vec<u8>>,
+            metadata: None,
+            license: None,
+            thumb: None,
+        };
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("add_composable_resource", (0u32, 0u32, composable))
+            ),
+            ok
+        );
+        let slot = SlotResource {
+            base: 0u32,
+            src: None::<Vec<u8>>,
+            metadata: None,
+            slot: 1u32,
+            license: None,
+            thumb: None,
+        };
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("add_slot_resource", (0u32, 1u32, slot))
+            ),
+            ok
+        );
+        let into_nft_0 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 0u32);
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, message("send", (0u32, 1u32, into_nft_0))),
+            ok
+        );
+
+        let equip = message("equip", ((0u32, 1u32), (0u32, 0u32), 0u32, 0u32, 1u32));
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, equip.clone()),
+            ok
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, equip),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::AlreadyEquipped))
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("equip", ((0u32, 1u32), (0u32, 0u32), 0u32, 0u32, 0u32))
+            ),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::NotEquippable))
+        );
+
+        let unequip = message("unequip", ((0u32, 1u32), (0u32, 0u32), 0u32, 1u32));
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, unequip.clone()),
+            ok
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(&alice, &rmrk, unequip),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::NotEquipped))
+        );
+
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message(
+                    "equippable",
+                    (0u32, 1u32, EquippableList::<Vec<u32>>::Empty)
+                )
+            ),
+            ok
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("change_base_issuer", (0u32, alice.clone()))
+            ),
+            ok
+        );
+        assert_eq!(
+            call::<Result<(), RmrkError>>(
+                &alice,
+                &rmrk,
+                message("equippable", (0u32, 1u32, EquippableList::<Vec<u32>>::All))
+            ),
+            Err(RmrkError::ErrorCode(RmrkErrorCode::NoPermission))
+        );
+    });
+}
+
+#[test]
+fn psp34_messages_act_for_the_caller() {
+    new_test_ext().execute_with(|| {
+        let (alice, bob) = (account(1), account(3));
+        setup(&alice);
+        let rmrk = instantiate(&alice, wasm(RMRK_WASM), message("new", ()));
+        let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+        assert_eq!(
+            call::<Result<u32, RmrkError>>(&alice, &rmrk, message("create_collection", create)),
+            Ok(0)
+        );
+        let mint = (
+            alice.clone(),
+            0u32,
+            None::<AccountId>,
+            None::<Permill>,
+            b"ipfs://nft".to_vec(),
+            true,
+            None::<Vec<UnboundedResource>>,
+        );
+        assert_eq!(
+            call::<Result<(u32, u32), RmrkError>>(&alice, &rmrk, message("mint_ntf", mint)),
+            Ok((0, 0))
+        );
+
+        let psp34 = instantiate(&alice, wasm(PSP34_WASM), message("new", 0u32));
+        allow_caller_signing(&psp34);
+        let balance_of = |owner: &AccountId| {
+            call::<u32>(&alice, &psp34, message("PSP34::balance_of", owner.clone()))
+        };
+        assert_eq!(balance_of(&alice), 1);
+        assert_eq!(
+            call::<Result<(), PSP34Error>>(
+                &alice,
+                &psp34,
+                message(
+                    "PSP34::transfer",
+                    (bob.clone(), Id::U64(0), Vec::<u8>::new())
+                )
+            ),
+            Ok(())
+        );
+        assert_eq!(balance_of(&alice), 0);
+        assert_eq!(balance_of(&bob), 1);
+
+        // An approved token is held by the contract, which sends it for the operator.
+        let approve = (alice.clone(), Some(Id::U64(0)), true);
+        assert_eq!(
+            call::<Result<(), PSP34Error>>(&bob, &psp34, message("PSP34::approve", approve)),
+            Ok(())
+        );
+        assert_eq!(
+            call::<Option<AccountId>>(&alice, &psp34, message("PSP34::owner_of", Id::U64(0))),
+            Some(bob.clone())
+        );
+        assert_eq!(balance_of(&bob), 1);
+        assert_eq!(
+            call::<Result<(), PSP34Error>>(
+                &alice,
+                &psp34,
+                message(
+                    "PSP34::transfer",
+                    (alice.clone(), Id::U64(0), Vec::<u8>::new())
+                )
+            ),
+            Ok(())
+        );
+        assert_eq!(balance_of(&alice), 1);
+        assert_eq!(balance_of(&bob), 0);
+    });
+}
+
+#[test]
+fn psp22_messages_act_for_the_caller() {
+    new_test_ext().execute_with(|| {
+        let (alice, bob) = (account(1), account(3));
+        setup(&alice);
+        let psp22 = instantiate(&alice, wasm(PSP22_WASM), message("new", 1 as AssetId));
+        allow_caller_signing(&psp22);
+        // Issued by the contract, and sufficient so that holders need no native balance.
+        assert_ok!(Assets::force_create(Origin::root(), 1, psp22.clone().into(), true, 1));
+        let balance_of = |owner: &AccountId| {
+            call::<Balance>(&alice, &psp22, message("PSP22::balance_of", owner.clone()))
+        };
+
+        assert_eq!(
+            call::<Result<(), PSP22Error>>(
+                &alice,
+                &psp22,
+                message("PSP22Mintable::mint", (alice.clone(), 100 as Balance))
+            ),
+            Ok(())
+        );
+        assert_eq!(call::<Balance>(&alice, &psp22, message("PSP22::total_supply", ())), 100);
+
+        // Moved from the balance of the caller, not of the contract.
+        let transfer = |amount: Balance| {
+            message("PSP22::transfer", (bob.clone(), amount, Vec::<u8>::new()))
+        };
+        assert_eq!(call::<Result<(), PSP22Error>>(&alice, &psp22, transfer(30)), Ok(()));
+        assert_eq!(
+            call::<Result<(), PSP22Error>>(&alice, &psp22, transfer(100)),
+            Err(PSP22Error::InsufficientBalance)
+        );
+        assert_eq!(balance_of(&alice), 70);
+        assert_eq!(balance_of(&bob), 30);
+
+        assert_eq!(
+            call::<Result<(), PSP22Error>>(
+                &alice,
+                &psp22,
+                message("PSP22::approve", (bob.clone(), 20 as Balance))
+            ),
+            Ok(())
+        );
+        let transfer_from = |amount: Balance| {
+            message(
+                "PSP22::transfer_from",
+                (alice.clone(), bob.clone(), amount, Vec::<u8>::new()),
+            )
+        };
+        assert_eq!(
+            call::<Result<(), PSP22Error>>(&bob, &psp22, transfer_from(21)),
+            Err(PSP22Error::InsufficientAllowance)
+        );
+        assert_eq!(call::<Result<(), PSP22Error>>(&bob, &psp22, transfer_from(15)), Ok(()));
+        assert_eq!(
+            call::<Balance>(
+                &alice,
+                &psp22,
+                message("PSP22::allowance", (alice.clone(), bob.clone()))
+            ),
+            5
+        );
+        assert_eq!(balance_of(&bob), 45);
+
+        // Only the account which instantiated the contract mints and burns.
+        let burn = message("PSP22Burnable::burn", (bob.clone(), 45 as Balance));
+        assert_eq!(
+            call::<Result<(), PSP22Error>>(&bob, &psp22, burn.clone()),
+            Err(PSP22Error::Custom(String::from("Caller is not the owner")))
+        );
+        assert_eq!(call::<Result<(), PSP22Error>>(&alice, &psp22, burn), Ok(()));
+        assert_eq!(Assets::total_supply(1), 55);
+    });
+}
+
+#[test]
+fn randomness_message_reaches_the_pallet() {
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let randomness = instantiate(&alice, wasm(RANDOMNESS_WASM), message("new", ()));
+        let subject = [7u8; 32];
+
+        assert_eq!(
+            call::<Result<(Hash, BlockNumber), RandomnessError>>(
+                &alice,
+                &randomness,
+                message("random", subject)
+            ),
+            Ok(RandomnessCollectiveFlip::random(&subject))
+        );
+    });
+}
+
+#[test]
+fn randomness_commitment_is_decided_by_its_target_block() {
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let randomness = instantiate(&alice, wasm(RANDOMNESS_WASM), message("new", ()));
+        let secret = [42u8; 32];
+        let commitment = Hash::from(sp_core::blake2_256(&secret));
+        let reveal = |secret: [u8; 32]| {
+            call::<Result<Hash, RandomnessError>>(&alice, &randomness, message("reveal", secret))
+        };
+        let commit = || {
+            call::<Result<(), RandomnessError>>(&alice, &randomness, message("commit", commitment))
+        };
+        let advance = |blocks| System::set_block_number(System::block_number() + blocks);
+        advance(RANDOM_MATERIAL_LEN);
+        assert_eq!(
+            RandomnessCollectiveFlip::random(&secret).1,
+            System::block_number() - RANDOM_MATERIAL_LEN
+        );
+
+        assert_eq!(reveal(secret), Err(RandomnessError::NoCommitment));
+        assert_eq!(commit(), Ok(()));
+        assert_eq!(reveal([43; 32]), Err(RandomnessError::CommitmentMismatch));
+        assert_eq!(reveal(secret), Err(RandomnessError::TooEarly));
+
+        // The seed of the target block, one block after the commitment, is only out
+        // `RANDOM_MATERIAL_LEN` blocks later.
+        advance(RANDOM_MATERIAL_LEN + 1);
+        let (seed, _) = RandomnessCollectiveFlip::random(&secret);
+        let outcome = sp_core::blake2_256(&[seed.as_ref(), &secret[..]].concat());
+        assert_eq!(reveal(secret), Ok(Hash::from(outcome)));
+        assert_eq!(reveal(secret), Err(RandomnessError::NoCommitment));
+
+        // The seed of the first block after the reveal window is too late.
+        assert_eq!(commit(), Ok(()));
+        advance(RANDOM_MATERIAL_LEN + 1 + REVEAL_WINDOW);
+        assert_eq!(reveal(secret), Err(RandomnessError::Expired));
+    });
+}
+
+#[test]
+fn unknown_func_id_traps_the_contract() {
+    // No example contract calls an unknown ID, this one calls 2001 on every message.
+    let code = wat::parse_str(
+        r#"
+        (module
+            (import "seal0" "seal_call_chain_extension"
+                (func $call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+            (import "env" "memory" (memory 1 1))
+            ;; Room for 16 bytes of output at offset 4
+            (data (i32.const 0) "\10")
+            (func (export "deploy"))
+            (func (export "call")
+                (drop (call $call_chain_extension
+                    (i32.const 2001) (i32.const 0) (i32.const 0) (i32.const 4) (i32.const 0)))
+            )
+        )
+        "#,
+    )
+    .expect("valid wat");
+
+    new_test_ext().execute_with(|| {
+        let alice = account(1);
+        setup(&alice);
+        let contract = instantiate(&alice, code, Vec::new());
+        let result =
+            Contracts::bare_call(alice, contract, 0, GAS_LIMIT, None, Vec::new(), true).result;
+        assert_eq!(
+            result.map(|_| ()),
+            Err(DispatchError::Other("Unimplemented func_id"))
+        );
+    });
+}
@@ -6,9 +6,13 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use assets_chain_extension_types::AssetId;
+use chain_extension_weights::WeightInfo as _;
 use codec::{Decode, Encode};
+use dapps_staking_chain_extension_types::StatusCode;
 use frame_support::{
     construct_runtime, parameter_types,
+    storage::{with_transaction, TransactionOutcome},
     traits::{Currency, FindAuthor, Get, KeyOwnerProofSystem, Nothing},
     weights::{
         constants::{RocksDbWeight, WEIGHT_PER_SECOND},
@@ -38,8 +42,7 @@ use sp_std::prelude::*;
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
-use frame_support::log::{error, trace};
-use frame_support::traits::Randomness;
+use frame_support::log::trace;
 pub use pallet_balances::Call as BalancesCall;
 use pallet_contracts::chain_extension::{
     ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
@@ -53,7 +56,15 @@ pub use sp_runtime::BuildStorage;
 use sp_runtime::DispatchError;
 pub use sp_runtime::{Perbill, Permill};
 
-mod weights;
+use chain_extensions::{
+    AssetsExtension, CallOutcome, DappsStakingExtension, Origins, RandomnessExtension,
+    RmrkExtension, RuntimeExtension,
+};
+
+mod chain_extension_weights;
+mod chain_extensions;
+#[cfg(test)]
+mod contract_tests;
 
 #[cfg(feature = "std")]
 /// Wasm binary unwrapped. If built with `BUILD_DUMMY_WASM_BINARY`, the function panics.
@@ -327,7 +338,7 @@ impl pallet_dapps_staking::Config for Runtime {
     type SmartContract = SmartContract<AccountId>;
     type RegisterDeposit = RegisterDeposit;
     type Event = Event;
-    type WeightInfo = weights::pallet_dapps_staking::WeightInfo<Runtime>;
+    type WeightInfo = pallet_dapps_staking::weights::SubstrateWeight<Runtime>;
     type MaxNumberOfStakersPerContract = MaxNumberOfStakersPerContract;
     type MinimumStakingAmount = MinimumStakingAmount;
     type PalletId = DappsStakingPalletId;
@@ -353,20 +364,21 @@ impl<AccountId> Default for SmartContract<AccountId> {
 }
 
 #[cfg(not(feature = "runtime-benchmarks"))]
-impl<AccountId> pallet_dapps_staking::traits::IsContract for SmartContract<AccountId> {
+impl pallet_dapps_staking::traits::IsContract for SmartContract<AccountId> {
     fn is_valid(&self) -> bool {
         match self {
-            SmartContract::Wasm(_account) => false,
+            // Storage of an account can only be read if it holds a contract instance.
+            SmartContract::Wasm(account) => Contracts::get_storage(account.clone(), [0; 32]).is_ok(),
             SmartContract::Evm(account) => EVM::account_codes(&account).len() > 0,
         }
     }
 }
 
 #[cfg(feature = "runtime-benchmarks")]
-impl<AccountId> pallet_dapps_staking::traits::IsContract for SmartContract<AccountId> {
+impl pallet_dapps_staking::traits::IsContract for SmartContract<AccountId> {
     fn is_valid(&self) -> bool {
         match self {
-            SmartContract::Wasm(_account) => false,
+            SmartContract::Wasm(_account) => true,
             SmartContract::Evm(_account) => true,
         }
     }
@@ -565,77 +577,242 @@ impl pallet_sudo::Config for Runtime {
     type Call = Call;
 }
 
+parameter_types! {
+    pub const ClassDeposit: Balance = 10 * MILLIAST;
+    pub const InstanceDeposit: Balance = MILLIAST;
+    pub const UniquesMetadataDepositBase: Balance = deposit(1, 129);
+    pub const AttributeDepositBase: Balance = deposit(1, 0);
+    pub const UniquesDepositPerByte: Balance = deposit(0, 1);
+    pub const UniquesStringLimit: u32 = 128;
+    pub const KeyLimit: u32 = 32;
+    pub const ValueLimit: u32 = 256;
+}
+
+impl pallet_uniques::Config for Runtime {
+    type Event = Event;
+    type ClassId = u32;
+    type InstanceId = u32;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type ClassDeposit = ClassDeposit;
+    type InstanceDeposit = InstanceDeposit;
+    type MetadataDepositBase = UniquesMetadataDepositBase;
+    type AttributeDepositBase = AttributeDepositBase;
+    type DepositPerByte = UniquesDepositPerByte;
+    type StringLimit = UniquesStringLimit;
+    type KeyLimit = KeyLimit;
+    type ValueLimit = ValueLimit;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxRecursions: u32 = 10;
+    pub const ResourceSymbolLimit: u32 = 10;
+    pub const PartsLimit: u32 = 25;
+    pub const MaxPriorities: u32 = 25;
+    pub const CollectionSymbolLimit: u32 = 100;
+    pub const MaxResourcesOnMint: u32 = 100;
+}
+
+impl pallet_rmrk_core::Config for Runtime {
+    type Event = Event;
+    type ProtocolOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxRecursions = MaxRecursions;
+    type ResourceSymbolLimit = ResourceSymbolLimit;
+    type PartsLimit = PartsLimit;
+    type MaxPriorities = MaxPriorities;
+    type CollectionSymbolLimit = CollectionSymbolLimit;
+    type MaxResourcesOnMint = MaxResourcesOnMint;
+}
+
+parameter_types! {
+    pub const MaxPropertiesPerTheme: u32 = 100;
+    pub const MaxCollectionsEquippablePerPart: u32 = 100;
+}
+
+impl pallet_rmrk_equip::Config for Runtime {
+    type Event = Event;
+    type MaxPropertiesPerTheme = MaxPropertiesPerTheme;
+    type MaxCollectionsEquippablePerPart = MaxCollectionsEquippablePerPart;
+}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 10 * AST;
+    pub const AssetAccountDeposit: Balance = deposit(1, 18);
+    pub const AssetsMetadataDepositBase: Balance = deposit(1, 68);
+    pub const AssetsMetadataDepositPerByte: Balance = deposit(0, 1);
+    pub const ApprovalDeposit: Balance = MILLIAST;
+    pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = AssetId;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    type MetadataDepositBase = AssetsMetadataDepositBase;
+    type MetadataDepositPerByte = AssetsMetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+/// A contract permitted to call privileged chain extension functions.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub enum AllowedContract {
+    /// Every instance of the code.
+    CodeHash(Hash),
+    /// A single instance.
+    Address(AccountId),
+}
+
+impl AllowedContract {
+    /// Whether this entry allows the contract at `address`, running `code_hash`.
+    fn matches(&self, address: &AccountId, code_hash: &Hash) -> bool {
+        match self {
+            AllowedContract::CodeHash(hash) => hash == code_hash,
+            AllowedContract::Address(account) => account == address,
+        }
+    }
+}
+
+parameter_types! {
+    // Governance switches of the chain extension. Sudo changes them with `system.set_storage`
+    // under the key `twox_128(b":Name:")`, e.g. `twox_128(b":DisabledExtensions:")`.
+
+    /// Off rejects every contract using the chain extension when its code is uploaded.
+    pub storage ChainExtensionEnabled: bool = true;
+    /// Extensions whose calls trap, by the hundreds of their function IDs, e.g. 35 for RMRK.
+    pub storage DisabledExtensions: Vec<u32> = Vec::new();
+    /// Contracts allowed to call privileged functions, every contract is if `None`.
+    pub storage PrivilegedContracts: Option<Vec<AllowedContract>> = None;
+    /// Contracts allowed to sign with the account calling them, none by default.
+    pub storage CallerSigningContracts: Vec<AllowedContract> = Vec::new();
+}
+
 /// Contract extension for Astar Local Chain-Extension
 pub struct LocalChainExtension;
 
+/// The extensions `LocalChainExtension` answers for, each owning a range of function IDs.
+pub type RuntimeExtensions =
+    (RandomnessExtension, DappsStakingExtension, RmrkExtension, AssetsExtension);
+
 impl ChainExtension<Runtime> for LocalChainExtension {
-    fn call<E: Ext>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+    fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
     where
+        E: Ext<T = Runtime>,
         <E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
     {
-        match func_id {
-            // RandomnessCollectiveFlip - randon()
-            1101 => {
-                let mut env = env.buf_in_buf_out();
-                let arg: [u8; 32] = env.read_as()?;
-                let random_seed = crate::RandomnessCollectiveFlip::random(&arg).0;
-                let random_slice = random_seed.encode();
-                trace!(
-                    target: "runtime",
-                    "[ChainExtension]|call|func_id:{:}",
-                    func_id
-                );
-                env.write(&random_slice, false, None)
-                    .map_err(|_| DispatchError::Other("ChainExtension failed to call random"))?;
+        let mut env = env.buf_in_buf_out();
+        env.charge_weight(ExtensionWeight::per_byte().saturating_mul(env.in_len().into()))?;
+        // `DisabledExtensions`, `PrivilegedContracts` and `CallerSigningContracts`
+        env.charge_weight(RocksDbWeight::get().reads(3))?;
+        let address = env.ext().address().clone();
+        let code_hash = *env.ext().own_code_hash();
+        Self::ensure_permitted(func_id, &address, &code_hash)?;
+        let caller = Self::signs_for_caller(&address, &code_hash)
+            .then(|| env.ext().caller().clone());
+        let input = env.read(env.in_len())?;
+        env.charge_weight(Self::weight(func_id, &input))?;
+        match Self::dispatch(func_id, &input, caller, address)? {
+            Ok(output) => {
+                env.write(&output, false, Some(ExtensionWeight::per_byte()))
+                    .map_err(|_| DispatchError::Other("ChainExtension failed to write output"))?;
+                Ok(RetVal::Converging(StatusCode::Success.into()))
             }
-
-            //DappsStaking - current_era()
-            2001 => {
-                let mut env = env.buf_in_buf_out();
-                let current_era = crate::DappsStaking::current_era();
-                let current_era_encoded = current_era.encode();
+            Err(status) => {
                 trace!(
                     target: "runtime",
-                    "[ChainExtension]|call|func_id:{:} current_era:{:?}",
+                    "[ChainExtension]|call|func_id:{:} status:{:?}",
                     func_id,
-                    &current_era_encoded
+                    status
                 );
-                env.write(&current_era_encoded, false, None).map_err(|_| {
-                    DispatchError::Other("ChainExtension failed to call current_era")
-                })?;
+                Ok(RetVal::Converging(status))
             }
+        }
+    }
 
-            // DappsStaking - general_era_info()
-            2002 => {
-                let mut env = env.buf_in_buf_out();
-                let arg: u32 = env.read_as()?;
-                let era_info = DappsStaking::general_era_info(arg)
-                    .ok_or(DispatchError::Other("general_era_info call failed"));
-                sp_std::if_std!{println!("era_info:{:?}", era_info)};
-                let era_info_encoded = era_info.encode();
-                sp_std::if_std!{println!("era_info_encoded:{:?}", era_info_encoded)};
-                trace!(
-                    target: "runtime",
-                    "[ChainExtension]|call|func_id:{:} era_info_encoded:{:?}, arg:{:?}",
-                    func_id,
-                    era_info_encoded,
-                    arg
-                );
-                env.write(&era_info_encoded, false, None).map_err(|_| {
-                    DispatchError::Other("ChainExtension failed to call general_era_info")
-                })?;
-            }
+    fn enabled() -> bool {
+        ChainExtensionEnabled::get()
+    }
+}
+
+type ExtensionWeight = chain_extension_weights::EstimatedWeight<Runtime>;
+
+impl LocalChainExtension {
+    /// Weight charged for `func_id` once its `input` is read, before it is dispatched.
+    ///
+    /// Unknown IDs are rejected by `dispatch` and cost nothing beyond the copied input.
+    fn weight(func_id: u32, input: &[u8]) -> Weight {
+        RuntimeExtensions::weight(func_id, input)
+    }
 
-            _ => {
-                error!("Called an unregistered `func_id`: {:}", func_id);
-                return Err(DispatchError::Other("Unimplemented func_id"));
+    /// Traps the contract at `address`, running `code_hash`, if governance does not let it
+    /// call `func_id`.
+    fn ensure_permitted(
+        func_id: u32,
+        address: &AccountId,
+        code_hash: &Hash,
+    ) -> Result<(), DispatchError> {
+        if DisabledExtensions::get().contains(&(func_id / 100)) {
+            return Err(DispatchError::Other("ChainExtension disabled"));
+        }
+        if !RuntimeExtensions::is_privileged(func_id) {
+            return Ok(());
+        }
+        match PrivilegedContracts::get() {
+            Some(allowed)
+                if !allowed.iter().any(|contract| contract.matches(address, code_hash)) =>
+            {
+                Err(DispatchError::Other("ChainExtension function not allowed"))
             }
+            _ => Ok(()),
         }
-        Ok(RetVal::Converging(0))
     }
 
-    fn enabled() -> bool {
-        true
+    /// Whether the contract at `address`, running `code_hash`, may sign with its caller.
+    fn signs_for_caller(address: &AccountId, code_hash: &Hash) -> bool {
+        CallerSigningContracts::get()
+            .iter()
+            .any(|contract| contract.matches(address, code_hash))
+    }
+
+    /// Executes `func_id` with the SCALE encoded `input` and returns the SCALE encoded output.
+    ///
+    /// `caller` and `address` are the accounts the calling contract may sign dispatchables with,
+    /// `caller` is `None` unless the contract may sign with it.
+    /// Expected failures are reported as a `StatusCode`, an `Err` traps the contract.
+    fn dispatch(
+        func_id: u32,
+        input: &[u8],
+        caller: Option<AccountId>,
+        address: AccountId,
+    ) -> Result<CallOutcome, DispatchError> {
+        Self::in_transaction(|| {
+            RuntimeExtensions::dispatch(func_id, input, &Origins { caller, address })
+        })
+    }
+
+    /// Runs `dispatch` in a storage transaction, committed only if it reports success.
+    ///
+    /// A status code does not revert the contract, so a call failing halfway, e.g. an
+    /// `AssetsFunc::Approve` whose new approval fails after the old one was cancelled,
+    /// would otherwise keep its first writes.
+    fn in_transaction(
+        dispatch: impl FnOnce() -> Result<CallOutcome, DispatchError>,
+    ) -> Result<CallOutcome, DispatchError> {
+        with_transaction(|| {
+            let result = dispatch();
+            match result {
+                Ok(Ok(_)) => TransactionOutcome::Commit(result),
+                _ => TransactionOutcome::Rollback(result),
+            }
+        })
     }
 }
 
@@ -662,6 +839,10 @@ construct_runtime!(
         BaseFee: pallet_base_fee::{Pallet, Call, Storage, Config<T>, Event},
         Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>},
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+        Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
+        RmrkCore: pallet_rmrk_core::{Pallet, Call, Storage, Event<T>},
+        RmrkEquip: pallet_rmrk_equip::{Pallet, Call, Storage, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -1155,3 +1336,942 @@ impl_runtime_apis! {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_extensions::UnboundedResource;
+    use assets_chain_extension_types::{AssetsFunc, AssetsOrigin, StatusCode as AssetsStatusCode};
+    use dapps_staking_chain_extension_types::{DappsStakingFunc, StakingOrigin};
+    use frame_support::{assert_ok, traits::OnInitialize};
+    use rmrk_chain_extension_types::{RmrkFunc, StatusCode as RmrkStatusCode};
+    use rmrk_traits::{
+        AccountIdOrCollectionNftTuple, BasicResource, CollectionId, ComposableResource,
+        EquippableList, FixedPart, PartType, RoyaltyInfo, SlotPart, SlotResource, Theme,
+        ThemeProperty,
+    };
+
+    pub(crate) fn account(seed: u8) -> AccountId {
+        AccountId::from([seed; 32])
+    }
+
+    pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+        GenesisConfig::default()
+            .build_storage()
+            .expect("default genesis builds")
+            .into()
+    }
+
+    /// Funds the contract at `account(2)` for the deposits its calls take, and returns it
+    /// with a function calling the chain extension as that contract, signing with `caller`.
+    pub(crate) fn funded_contract<F: Into<u32>>(
+        caller: Option<AccountId>,
+    ) -> (AccountId, impl Fn(F, Vec<u8>) -> Result<CallOutcome, DispatchError>) {
+        let contract = account(2);
+        Balances::make_free_balance_be(&contract, 1_000 * AST);
+        let address = contract.clone();
+        let dispatch = move |func: F, input: Vec<u8>| {
+            LocalChainExtension::dispatch(func.into(), &input, caller.clone(), address.clone())
+        };
+        (contract, dispatch)
+    }
+
+    #[test]
+    fn every_declared_func_id_is_dispatched() {
+        use codec::Compact;
+
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            DappsStaking::on_initialize(1);
+            let era = DappsStaking::current_era();
+            // An EVM account with code passes `IsContract` without deploying a Wasm blob.
+            let evm = H160::repeat_byte(1);
+            pallet_evm::AccountCodes::<Runtime>::insert(evm, vec![0]);
+            let contract = SmartContract::<AccountId>::Evm(evm);
+
+            let (developer, dispatch) = funded_contract::<DappsStakingFunc>(None);
+            let mut dispatched = Vec::new();
+            let mut call = |func, input: Vec<u8>| {
+                dispatched.push(func);
+                dispatch(func, input)
+            };
+            let stake = MinimumStakingAmount::get();
+
+            assert_eq!(call(DappsStakingFunc::CurrentEra, Vec::new()), Ok(Ok(era.encode())));
+            assert_eq!(
+                call(DappsStakingFunc::GeneralEraInfo, era.encode()),
+                Ok(Ok(DappsStaking::general_era_info(era).unwrap().encode()))
+            );
+            assert_eq!(
+                call(DappsStakingFunc::Register, (StakingOrigin::Address, contract).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                call(
+                    DappsStakingFunc::BondAndStake,
+                    (StakingOrigin::Address, contract, stake).encode()
+                ),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                call(DappsStakingFunc::StakedAmount, (developer.clone(), contract).encode()),
+                Ok(Ok(stake.encode()))
+            );
+            // `ContractStakeInfo` and `UnlockingChunk` as the `ds` contract decodes them.
+            let contract_stake = call(DappsStakingFunc::ContractStake, (contract, era).encode());
+            assert_eq!(
+                Option::<(Compact<Balance>, Compact<u32>, bool)>::decode(
+                    &mut &contract_stake.unwrap().unwrap()[..]
+                )
+                .unwrap(),
+                Some((stake.into(), 1.into(), false))
+            );
+            // Rewards of the current era are not known before it ends.
+            assert_eq!(
+                call(DappsStakingFunc::ClaimStaker, (StakingOrigin::Address, contract).encode()),
+                Ok(Err(StatusCode::EraNotFound.into()))
+            );
+            assert_eq!(
+                call(DappsStakingFunc::ClaimDapp, (StakingOrigin::Address, contract, era).encode()),
+                Ok(Err(StatusCode::EraNotFound.into()))
+            );
+            assert_eq!(
+                call(
+                    DappsStakingFunc::UnbondAndUnstake,
+                    (StakingOrigin::Address, contract, stake).encode()
+                ),
+                Ok(Ok(Vec::new()))
+            );
+            let unbonding = call(DappsStakingFunc::UnbondingInfo, developer.encode());
+            assert_eq!(
+                Vec::<(Compact<Balance>, Compact<u32>)>::decode(
+                    &mut &unbonding.unwrap().unwrap()[..]
+                )
+                .unwrap(),
+                vec![(stake.into(), (era + UnbondingPeriod::get()).into())]
+            );
+            // The unbonded chunk unlocks `UnbondingPeriod` eras later.
+            assert_eq!(
+                call(DappsStakingFunc::WithdrawUnbonded, StakingOrigin::Address.encode()),
+                Ok(Err(StatusCode::NothingToWithdraw.into()))
+            );
+
+            assert!(
+                DappsStakingFunc::ALL.iter().all(|func| dispatched.contains(func)),
+                "{:?} are not all dispatched",
+                DappsStakingFunc::ALL
+            );
+        });
+    }
+
+    #[test]
+    fn wasm_contract_validity_checks_pallet_contracts() {
+        use pallet_dapps_staking::traits::IsContract;
+
+        new_test_ext().execute_with(|| {
+            assert!(!SmartContract::Wasm(account(2)).is_valid());
+        });
+    }
+
+    #[test]
+    fn every_declared_func_id_is_weighted() {
+        for func in DappsStakingFunc::ALL {
+            assert!(
+                LocalChainExtension::weight((*func).into(), &[]) > 0,
+                "{:?} is free",
+                func
+            );
+        }
+        for func in RmrkFunc::ALL {
+            assert!(
+                LocalChainExtension::weight((*func).into(), &[]) > 0,
+                "{:?} is free",
+                func
+            );
+        }
+        for func in AssetsFunc::ALL {
+            assert!(
+                LocalChainExtension::weight((*func).into(), &[]) > 0,
+                "{:?} is free",
+                func
+            );
+        }
+        assert!(LocalChainExtension::weight(1101, &[]) > 0);
+    }
+
+    #[test]
+    fn mint_weight_scales_with_the_resources() {
+        let mint_weight = |resources: usize| {
+            let resource = UnboundedResource::Basic(BasicResource {
+                src: Some(b"ipfs://resource".to_vec()),
+                metadata: None,
+                license: None,
+                thumb: None,
+            });
+            let mint = (
+                account(2),
+                0u32,
+                None::<AccountId>,
+                None::<Permill>,
+                b"ipfs://nft".to_vec(),
+                true,
+                Some(vec![resource; resources]),
+            );
+            LocalChainExtension::weight(RmrkFunc::MintNft.into(), &mint.encode())
+        };
+
+        assert_eq!(mint_weight(0), ExtensionWeight::mint_nft(0));
+        assert_eq!(mint_weight(100), ExtensionWeight::mint_nft(100));
+        assert!(mint_weight(100) > mint_weight(1));
+        // Input `dispatch` cannot decode is charged as a mint without resources.
+        assert_eq!(
+            LocalChainExtension::weight(RmrkFunc::MintNft.into(), &[1, 2, 3]),
+            ExtensionWeight::mint_nft(0)
+        );
+    }
+
+    #[test]
+    fn expected_failures_return_status_codes() {
+        new_test_ext().execute_with(|| {
+            let (_, dispatch) = funded_contract::<DappsStakingFunc>(Some(account(1)));
+
+            assert_eq!(
+                dispatch(DappsStakingFunc::GeneralEraInfo, 100u32.encode()),
+                Ok(Err(StatusCode::EraNotFound.into()))
+            );
+            assert_eq!(
+                dispatch(
+                    DappsStakingFunc::BondAndStake,
+                    (
+                        StakingOrigin::Caller,
+                        SmartContract::Wasm(account(2)),
+                        MinimumStakingAmount::get(),
+                    )
+                        .encode()
+                ),
+                Ok(Err(StatusCode::NotRegistered.into()))
+            );
+        });
+    }
+
+    #[test]
+    fn general_era_info_is_written_without_wrapper() {
+        new_test_ext().execute_with(|| {
+            // Era info is recorded on era change, past era 0.
+            System::set_block_number(1);
+            DappsStaking::on_initialize(1);
+            let era = DappsStaking::current_era();
+            let era_info = DappsStaking::general_era_info(era).expect("recorded on era change");
+
+            let (_, dispatch) = funded_contract(None);
+            let output = dispatch(DappsStakingFunc::GeneralEraInfo, era.encode());
+            assert_eq!(output, Ok(Ok(era_info.encode())));
+            let decoded: pallet_dapps_staking::EraInfo<Balance> =
+                Decode::decode(&mut &output.unwrap().unwrap()[..]).unwrap();
+            assert_eq!(decoded, era_info);
+        });
+    }
+
+    #[test]
+    fn rmrk_collection_and_nft_are_created() {
+        new_test_ext().execute_with(|| {
+            let (contract, dispatch) = funded_contract::<RmrkFunc>(None);
+
+            assert_eq!(
+                dispatch(RmrkFunc::CollectionIndex, Vec::new()),
+                Ok(Ok(0u32.encode()))
+            );
+            assert_eq!(
+                dispatch(
+                    RmrkFunc::CreateCollection,
+                    (b"ipfs://collection".to_vec(), Some(10u32), b"ROO".to_vec()).encode()
+                ),
+                Ok(Ok(0u32.encode()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::CollectionIndex, Vec::new()),
+                Ok(Ok(1u32.encode()))
+            );
+
+            let mint = (
+                account(1),
+                0u32,
+                Some(account(1)),
+                Some(Permill::from_percent(5)),
+                b"ipfs://nft".to_vec(),
+                true,
+                None::<Vec<UnboundedResource>>,
+            );
+            assert_eq!(dispatch(RmrkFunc::MintNft, mint.encode()), Ok(Ok((0u32, 0u32).encode())));
+            assert_eq!(
+                dispatch(RmrkFunc::NextNftId, 0u32.encode()),
+                Ok(Ok(1u32.encode()))
+            );
+            assert_eq!(
+                RmrkCore::nfts(0, 0).and_then(|nft| nft.royalty),
+                Some(RoyaltyInfo {
+                    recipient: account(1),
+                    amount: Permill::from_percent(5),
+                })
+            );
+
+            // `Permill` decodes any `u32`, the extension rejects more than one whole.
+            let royalty_out_of_range = (
+                account(1),
+                0u32,
+                None::<AccountId>,
+                Some(1_000_001u32),
+                b"ipfs://nft".to_vec(),
+                true,
+                None::<Vec<UnboundedResource>>,
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::MintNft, royalty_out_of_range.encode()),
+                Ok(Err(RmrkStatusCode::RoyaltyOutOfRange.into()))
+            );
+
+            // Only the issuer, the contract, may mint into the collection.
+            assert_eq!(
+                LocalChainExtension::dispatch(
+                    RmrkFunc::MintNft.into(),
+                    &mint.encode(),
+                    None,
+                    account(3),
+                ),
+                Ok(Err(RmrkStatusCode::NoPermission.into()))
+            );
+        });
+    }
+
+    #[test]
+    fn rmrk_nft_lifecycle_works() {
+        new_test_ext().execute_with(|| {
+            let (contract, dispatch) = funded_contract::<RmrkFunc>(None);
+            let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+            assert_eq!(dispatch(RmrkFunc::CreateCollection, create.encode()), Ok(Ok(0u32.encode())));
+            for nft_id in 0..2u32 {
+                let mint = (
+                    contract.clone(),
+                    0u32,
+                    None::<AccountId>,
+                    None::<Permill>,
+                    b"ipfs://nft".to_vec(),
+                    true,
+                    None::<Vec<UnboundedResource>>,
+                );
+                assert_eq!(
+                    dispatch(RmrkFunc::MintNft, mint.encode()),
+                    Ok(Ok((0u32, nft_id).encode()))
+                );
+            }
+
+            let into_nft_1 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 1u32);
+            assert_eq!(
+                dispatch(RmrkFunc::Send, (0u32, 1u32, into_nft_1.clone()).encode()),
+                Ok(Err(RmrkStatusCode::InvalidDestination.into()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::Send, (0u32, 0u32, into_nft_1).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::BurnNft, (0u32, 7u32).encode()),
+                Ok(Err(RmrkStatusCode::NftUnknown.into()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::BurnNft, (0u32, 1u32).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            // Burning the parent burns the nested child as well.
+            assert!(RmrkCore::nfts(0, 0).is_none());
+
+            assert_eq!(
+                dispatch(RmrkFunc::ChangeCollectionIssuer, (0u32, account(1)).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                RmrkCore::collections(0).map(|collection| collection.issuer),
+                Some(account(1))
+            );
+        });
+    }
+
+    #[test]
+    fn rmrk_resources_work() {
+        new_test_ext().execute_with(|| {
+            let (contract, dispatch) = funded_contract::<RmrkFunc>(None);
+            let basic = |src: &[u8]| BasicResource {
+                src: Some(src.to_vec()),
+                metadata: None,
+                license: None,
+                thumb: None,
+            };
+            let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+            assert_eq!(dispatch(RmrkFunc::CreateCollection, create.encode()), Ok(Ok(0u32.encode())));
+            let mint = (
+                contract.clone(),
+                0u32,
+                None::<AccountId>,
+                None::<Permill>,
+                b"ipfs://nft".to_vec(),
+                true,
+                Some(vec![UnboundedResource::Basic(basic(b"ipfs://0"))]),
+            );
+            assert_eq!(dispatch(RmrkFunc::MintNft, mint.encode()), Ok(Ok((0u32, 0u32).encode())));
+            assert!(pallet_rmrk_core::Resources::<Runtime>::contains_key((0, 0, 0)));
+
+            // The contract owns the NFT, so the resource is accepted right away.
+            assert_eq!(
+                dispatch(RmrkFunc::AddBasicResource, (0u32, 0u32, basic(b"ipfs://1")).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::AcceptResource, (0u32, 0u32, 1u32).encode()),
+                Ok(Err(RmrkStatusCode::ResourceNotPending.into()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::SetPriority, (0u32, 0u32, vec![1u32, 0]).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::SetPriority, (0u32, 0u32, vec![0u32; 26]).encode()),
+                Ok(Err(RmrkStatusCode::TooLong.into()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::RemoveResource, (0u32, 0u32, 9u32).encode()),
+                Ok(Err(RmrkStatusCode::ResourceUnknown.into()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::RemoveResource, (0u32, 0u32, 1u32).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert!(!pallet_rmrk_core::Resources::<Runtime>::contains_key((0, 0, 1)));
+        });
+    }
+
+    #[test]
+    fn rmrk_equip_works() {
+        new_test_ext().execute_with(|| {
+            let (contract, dispatch) = funded_contract::<RmrkFunc>(None);
+            let ok = Ok(Ok(Vec::new()));
+
+            let parts: Vec<PartType<Vec<u8>, Vec<CollectionId>>> = vec![
+                PartType::FixedPart(FixedPart { id: 0, z: 0, src: b"body".to_vec() }),
+                PartType::SlotPart(SlotPart {
+                    id: 1,
+                    equippable: EquippableList::All,
+                    src: b"hand".to_vec(),
+                    z: 1,
+                }),
+            ];
+            let base = (b"svg".to_vec(), b"KANARIA".to_vec(), parts);
+            assert_eq!(dispatch(RmrkFunc::CreateBase, base.encode()), ok);
+            let theme = |name: &[u8]| Theme {
+                name: name.to_vec(),
+                properties: vec![ThemeProperty { key: b"primary".to_vec(), value: b"red".to_vec() }],
+                inherit: false,
+            };
+            assert_eq!(
+                dispatch(RmrkFunc::ThemeAdd, (0u32, theme(b"dark")).encode()),
+                Ok(Err(RmrkStatusCode::NeedsDefaultTheme.into()))
+            );
+            assert_eq!(dispatch(RmrkFunc::ThemeAdd, (0u32, theme(b"default")).encode()), ok);
+
+            // NFT 0 is rendered with the base, NFT 1 fits into its slot part.
+            let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+            assert_eq!(dispatch(RmrkFunc::CreateCollection, create.encode()), Ok(Ok(0u32.encode())));
+            let composable = UnboundedResource::Composable(ComposableResource {
+                parts: vec![0, 1],
+                base: 0,
+                src: None,
+                metadata: None,
+                license: None,
+                thumb: None,
+            });
+            let slot = UnboundedResource::Slot(SlotResource {
+                base: 0,
+                src: None,
+                metadata: None,
+                slot: 1,
+                license: None,
+                thumb: None,
+            });
+            for (nft_id, resource) in [composable, slot].into_iter().enumerate() {
+                let mint = (
+                    contract.clone(),
+                    0u32,
+                    None::<AccountId>,
+                    None::<Permill>,
+                    b"ipfs://nft".to_vec(),
+                    true,
+                    Some(vec![resource]),
+                );
+                assert_eq!(
+                    dispatch(RmrkFunc::MintNft, mint.encode()),
+                    Ok(Ok((0u32, nft_id as u32).encode()))
+                );
+            }
+            let into_nft_0 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 0u32);
+            assert_eq!(dispatch(RmrkFunc::Send, (0u32, 1u32, into_nft_0).encode()), ok);
+
+            let equip = ((0u32, 1u32), (0u32, 0u32), 0u32, 0u32, 1u32);
+            assert_eq!(dispatch(RmrkFunc::Equip, equip.encode()), ok);
+            assert_eq!(
+                dispatch(RmrkFunc::Equip, equip.encode()),
+                Ok(Err(RmrkStatusCode::AlreadyEquipped.into()))
+            );
+            let fixed = ((0u32, 1u32), (0u32, 0u32), 0u32, 0u32, 0u32);
+            assert_eq!(
+                dispatch(RmrkFunc::Equip, fixed.encode()),
+                Ok(Err(RmrkStatusCode::NotEquippable.into()))
+            );
+
+            let unequip = ((0u32, 1u32), (0u32, 0u32), 0u32, 1u32);
+            assert_eq!(dispatch(RmrkFunc::Unequip, unequip.encode()), ok);
+            assert_eq!(
+                dispatch(RmrkFunc::Unequip, unequip.encode()),
+                Ok(Err(RmrkStatusCode::NotEquipped.into()))
+            );
+
+            assert_eq!(
+                dispatch(RmrkFunc::Equippable, (0u32, 1u32, EquippableList::<Vec<u32>>::Empty).encode()),
+                ok
+            );
+            assert_eq!(dispatch(RmrkFunc::ChangeBaseIssuer, (0u32, account(1)).encode()), ok);
+            assert_eq!(
+                dispatch(RmrkFunc::Equippable, (0u32, 1u32, EquippableList::<Vec<u32>>::All).encode()),
+                Ok(Err(RmrkStatusCode::NoPermission.into()))
+            );
+        });
+    }
+
+    #[test]
+    fn rmrk_queries_work() {
+        new_test_ext().execute_with(|| {
+            let (contract, dispatch) = funded_contract::<RmrkFunc>(None);
+            let ok = Ok(Ok(Vec::new()));
+
+            assert_eq!(dispatch(RmrkFunc::CollectionInfo, 0u32.encode()), Ok(Ok(None::<()>.encode())));
+            let create = (b"ipfs://collection".to_vec(), Some(5u32), b"ROO".to_vec());
+            assert_eq!(dispatch(RmrkFunc::CreateCollection, create.encode()), Ok(Ok(0u32.encode())));
+            for nft_id in 0..2u32 {
+                let mint = (
+                    contract.clone(),
+                    0u32,
+                    Some(account(1)),
+                    Some(Permill::from_percent(5)),
+                    b"ipfs://nft".to_vec(),
+                    true,
+                    None::<Vec<UnboundedResource>>,
+                );
+                assert_eq!(
+                    dispatch(RmrkFunc::MintNft, mint.encode()),
+                    Ok(Ok((0u32, nft_id).encode()))
+                );
+            }
+            let into_nft_0 = AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0u32, 0u32);
+            assert_eq!(dispatch(RmrkFunc::Send, (0u32, 1u32, into_nft_0).encode()), ok);
+
+            assert_eq!(
+                dispatch(RmrkFunc::CollectionInfo, 0u32.encode()),
+                Ok(Ok(RmrkCore::collections(0).encode()))
+            );
+            let collection = RmrkCore::collections(0).expect("collection was created");
+            assert_eq!(
+                (collection.issuer, collection.max, collection.nfts_count),
+                (contract.clone(), Some(5), 2)
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::NftInfo, (0u32, 1u32).encode()),
+                Ok(Ok(RmrkCore::nfts(0, 1).encode()))
+            );
+            let children = |start: Option<(u32, u32)>, limit: u32| {
+                dispatch(RmrkFunc::Children, (0u32, 0u32, start, limit).encode())
+            };
+            assert_eq!(children(None, u32::MAX), Ok(Ok(vec![(0u32, 1u32)].encode())));
+            assert_eq!(children(None, 0), Ok(Ok(Vec::<(u32, u32)>::new().encode())));
+            assert_eq!(children(Some((0, 1)), 1), Ok(Ok(Vec::<(u32, u32)>::new().encode())));
+            assert_eq!(
+                dispatch(RmrkFunc::Properties, (0u32, Some(0u32), None::<Vec<u8>>, 1u32).encode()),
+                Ok(Ok(Vec::<(Vec<u8>, Vec<u8>)>::new().encode()))
+            );
+        });
+    }
+
+    #[test]
+    fn rmrk_send_from_caller_acts_for_the_caller() {
+        new_test_ext().execute_with(|| {
+            let holder = account(1);
+            let (contract, dispatch) = funded_contract::<RmrkFunc>(Some(holder.clone()));
+            let create = (b"ipfs://collection".to_vec(), None::<u32>, b"ROO".to_vec());
+            assert_eq!(dispatch(RmrkFunc::CreateCollection, create.encode()), Ok(Ok(0u32.encode())));
+            let mint = (
+                holder.clone(),
+                0u32,
+                None::<AccountId>,
+                None::<Permill>,
+                b"ipfs://nft".to_vec(),
+                true,
+                None::<Vec<UnboundedResource>>,
+            );
+            assert_eq!(dispatch(RmrkFunc::MintNft, mint.encode()), Ok(Ok((0u32, 0u32).encode())));
+            assert_eq!(
+                dispatch(RmrkFunc::BalanceOf, (holder.clone(), 0u32, None::<u32>).encode()),
+                Ok(Ok((1u32, None::<u32>).encode()))
+            );
+
+            // The contract does not hold the NFT, only its caller may send it.
+            let to_3 = AccountIdOrCollectionNftTuple::AccountId(account(3));
+            assert_eq!(
+                dispatch(RmrkFunc::Send, (0u32, 0u32, to_3.clone()).encode()),
+                Ok(Err(RmrkStatusCode::NoPermission.into()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::SendFromCaller, (0u32, 0u32, to_3).encode()),
+                Ok(Ok(Vec::new()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::BalanceOf, (holder.clone(), 0u32, None::<u32>).encode()),
+                Ok(Ok((0u32, None::<u32>).encode()))
+            );
+            assert_eq!(
+                dispatch(RmrkFunc::BalanceOf, (account(3), 0u32, None::<u32>).encode()),
+                Ok(Ok((1u32, None::<u32>).encode()))
+            );
+        });
+    }
+
+    #[test]
+    fn unknown_func_id_is_rejected() {
+        new_test_ext().execute_with(|| {
+            assert_eq!(
+                LocalChainExtension::dispatch(2001, &[], None, account(2)),
+                Err(DispatchError::Other("Unimplemented func_id"))
+            );
+        });
+    }
+
+    #[test]
+    fn extensions_own_disjoint_ranges() {
+        for func_id in 0..10_000 {
+            let owners = [
+                RandomnessExtension::owns(func_id),
+                DappsStakingExtension::owns(func_id),
+                RmrkExtension::owns(func_id),
+                AssetsExtension::owns(func_id),
+            ];
+            assert!(owners.iter().filter(|owns| **owns).count() <= 1, "{} is shared", func_id);
+        }
+        assert!(RandomnessExtension::owns(chain_extensions::RANDOM));
+        assert!(DappsStakingFunc::ALL
+            .iter()
+            .all(|func| DappsStakingExtension::owns((*func).into())));
+        assert!(RmrkFunc::ALL.iter().all(|func| RmrkExtension::owns((*func).into())));
+        assert!(AssetsFunc::ALL.iter().all(|func| AssetsExtension::owns((*func).into())));
+    }
+
+    #[test]
+    fn extensions_answer_only_their_own_ids() {
+        new_test_ext().execute_with(|| {
+            let origins = Origins { caller: None, address: account(2) };
+            let current_era = DappsStakingFunc::CurrentEra.into();
+            assert_eq!(
+                DappsStakingExtension::dispatch(current_era, &[], &origins),
+                Ok(Ok(DappsStaking::current_era().encode()))
+            );
+            assert_eq!(
+                RmrkExtension::dispatch(current_era, &[], &origins),
+                Err(DispatchError::Other("Unimplemented func_id"))
+            );
+            assert_eq!(RmrkExtension::weight(current_era, &[]), 0);
+            // An undeclared ID in the range of an extension is rejected by it.
+            assert_eq!(
+                DappsStakingExtension::dispatch(3499, &[], &origins),
+                Err(DispatchError::Other("Unimplemented func_id"))
+            );
+            // A runtime composes only the extensions it enables.
+            assert_eq!(
+                <(RandomnessExtension, RmrkExtension)>::dispatch(current_era, &[], &origins),
+                Err(DispatchError::Other("Unimplemented func_id"))
+            );
+        });
+    }
+
+    #[test]
+    fn failed_calls_are_rolled_back() {
+        new_test_ext().execute_with(|| {
+            let disable = |outcome: CallOutcome| {
+                LocalChainExtension::in_transaction(|| {
+                    ChainExtensionEnabled::set(&false);
+                    Ok(outcome)
+                })
+            };
+
+            let failed = StatusCode::Failed.into();
+            assert_eq!(disable(Err(failed)), Ok(Err(failed)));
+            assert!(ChainExtensionEnabled::get());
+            assert_eq!(disable(Ok(Vec::new())), Ok(Ok(Vec::new())));
+            assert!(!ChainExtensionEnabled::get());
+        });
+    }
+
+    #[test]
+    fn governance_disables_extensions() {
+        new_test_ext().execute_with(|| {
+            assert!(LocalChainExtension::enabled());
+            ChainExtensionEnabled::set(&false);
+            assert!(!LocalChainExtension::enabled());
+
+            let permitted = |func_id| {
+                LocalChainExtension::ensure_permitted(func_id, &account(2), &Hash::repeat_byte(1))
+            };
+            let current_era = DappsStakingFunc::CurrentEra.into();
+            let collection_index: u32 = RmrkFunc::CollectionIndex.into();
+            assert_eq!(permitted(collection_index), Ok(()));
+
+            DisabledExtensions::set(&vec![collection_index / 100]);
+            assert_eq!(
+                permitted(collection_index),
+                Err(DispatchError::Other("ChainExtension disabled"))
+            );
+            assert_eq!(permitted(current_era), Ok(()));
+            assert_eq!(permitted(chain_extensions::RANDOM), Ok(()));
+        });
+    }
+
+    #[test]
+    fn privileged_functions_are_limited_to_allowed_contracts() {
+        new_test_ext().execute_with(|| {
+            let allowed_hash = Hash::repeat_byte(1);
+            let other_hash = Hash::repeat_byte(2);
+            let register = DappsStakingFunc::Register.into();
+            let create_collection = RmrkFunc::CreateCollection.into();
+            let permitted = |func_id, address: u8, code_hash| {
+                LocalChainExtension::ensure_permitted(func_id, &account(address), code_hash)
+            };
+
+            // Without an allow-list every contract is privileged.
+            assert_eq!(permitted(register, 2, &other_hash), Ok(()));
+
+            PrivilegedContracts::set(&Some(vec![
+                AllowedContract::CodeHash(allowed_hash),
+                AllowedContract::Address(account(3)),
+            ]));
+            assert_eq!(permitted(register, 2, &allowed_hash), Ok(()));
+            assert_eq!(permitted(create_collection, 3, &other_hash), Ok(()));
+            for func_id in [register, create_collection] {
+                assert_eq!(
+                    permitted(func_id, 2, &other_hash),
+                    Err(DispatchError::Other("ChainExtension function not allowed"))
+                );
+            }
+            assert_eq!(
+                permitted(RmrkFunc::SendFromCaller.into(), 2, &other_hash),
+                Err(DispatchError::Other("ChainExtension function not allowed"))
+            );
+            // Queries stay open to everyone.
+            assert_eq!(permitted(DappsStakingFunc::CurrentEra.into(), 2, &other_hash), Ok(()));
+            assert_eq!(permitted(chain_extensions::RANDOM, 2, &other_hash), Ok(()));
+        });
+    }
+
+    #[test]
+    fn caller_signing_is_limited_to_allowed_contracts() {
+        new_test_ext().execute_with(|| {
+            let allowed_hash = Hash::repeat_byte(1);
+            let other_hash = Hash::repeat_byte(2);
+
+            // Without an allow-list no contract signs with its caller.
+            assert!(!LocalChainExtension::signs_for_caller(&account(2), &allowed_hash));
+            CallerSigningContracts::set(&vec![
+                AllowedContract::CodeHash(allowed_hash),
+                AllowedContract::Address(account(3)),
+            ]);
+            assert!(LocalChainExtension::signs_for_caller(&account(2), &allowed_hash));
+            assert!(LocalChainExtension::signs_for_caller(&account(3), &other_hash));
+            assert!(!LocalChainExtension::signs_for_caller(&account(2), &other_hash));
+
+            let contract = SmartContract::Wasm(account(2));
+            let asset = 1 as AssetId;
+            let caller_signed: Vec<(u32, Vec<u8>)> = vec![
+                (
+                    DappsStakingFunc::BondAndStake.into(),
+                    (StakingOrigin::Caller, contract.clone(), 1 as Balance).encode(),
+                ),
+                (
+                    DappsStakingFunc::UnbondAndUnstake.into(),
+                    (StakingOrigin::Caller, contract.clone(), 1 as Balance).encode(),
+                ),
+                (DappsStakingFunc::WithdrawUnbonded.into(), StakingOrigin::Caller.encode()),
+                (
+                    DappsStakingFunc::ClaimStaker.into(),
+                    (StakingOrigin::Caller, contract.clone()).encode(),
+                ),
+                (
+                    DappsStakingFunc::ClaimDapp.into(),
+                    (StakingOrigin::Caller, contract.clone(), 0u32).encode(),
+                ),
+                (
+                    DappsStakingFunc::Register.into(),
+                    (StakingOrigin::Caller, contract).encode(),
+                ),
+                (
+                    RmrkFunc::SendFromCaller.into(),
+                    (0u32, 0u32, AccountIdOrCollectionNftTuple::AccountId(account(3))).encode(),
+                ),
+                (
+                    AssetsFunc::Create.into(),
+                    (AssetsOrigin::Caller, asset, account(1), 1 as Balance).encode(),
+                ),
+                (
+                    AssetsFunc::Mint.into(),
+                    (AssetsOrigin::Caller, asset, account(1), 1 as Balance).encode(),
+                ),
+                (
+                    AssetsFunc::Burn.into(),
+                    (AssetsOrigin::Caller, asset, account(1), 1 as Balance).encode(),
+                ),
+                (
+                    AssetsFunc::Transfer.into(),
+                    (AssetsOrigin::Caller, asset, account(3), 1 as Balance).encode(),
+                ),
+                (
+                    AssetsFunc::Approve.into(),
+                    (AssetsOrigin::Caller, asset, account(3), 1 as Balance).encode(),
+                ),
+                (
+                    AssetsFunc::TransferApproved.into(),
+                    (AssetsOrigin::Caller, asset, account(1), account(3), 1 as Balance).encode(),
+                ),
+            ];
+            let (_, dispatch) = funded_contract::<u32>(None);
+            for (func_id, input) in caller_signed {
+                assert_eq!(
+                    dispatch(func_id, input),
+                    Err(DispatchError::Other("ChainExtension caller origin not allowed")),
+                    "{} signs with the caller",
+                    func_id
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn writes_and_issuer_actions_are_privileged() {
+        assert!(RuntimeExtensions::is_privileged(DappsStakingFunc::BondAndStake.into()));
+        assert!(!RuntimeExtensions::is_privileged(DappsStakingFunc::StakedAmount.into()));
+        assert!(RuntimeExtensions::is_privileged(RmrkFunc::MintNft.into()));
+        assert!(RuntimeExtensions::is_privileged(RmrkFunc::ThemeAdd.into()));
+        assert!(!RuntimeExtensions::is_privileged(RmrkFunc::AcceptNft.into()));
+        assert!(!RuntimeExtensions::is_privileged(RmrkFunc::Send.into()));
+        assert!(RuntimeExtensions::is_privileged(RmrkFunc::SendFromCaller.into()));
+        assert!(RuntimeExtensions::is_privileged(AssetsFunc::Transfer.into()));
+        assert!(!RuntimeExtensions::is_privileged(AssetsFunc::Allowance.into()));
+        assert!(!RuntimeExtensions::is_privileged(chain_extensions::RANDOM));
+        assert!(!RuntimeExtensions::is_privileged(2001));
+    }
+
+    #[test]
+    fn assets_round_trip_works() {
+        new_test_ext().execute_with(|| {
+            let (holder, spender) = (account(1), account(3));
+            let (contract, as_holder) = funded_contract::<AssetsFunc>(Some(holder.clone()));
+            let (_, as_spender) = funded_contract::<AssetsFunc>(Some(spender.clone()));
+            // Accounts of an asset which is not sufficient need a native balance.
+            for account in [&holder, &spender] {
+                Balances::make_free_balance_be(account, 1_000 * AST);
+            }
+            let ok = Ok(Ok(Vec::new()));
+            let status = |status: AssetsStatusCode| Ok(Err(status.into()));
+
+            // The contract creates and issues the asset, the holder moves it.
+            let create = (AssetsOrigin::Address, 1 as AssetId, contract.clone(), 10 as Balance);
+            assert_eq!(as_holder(AssetsFunc::Create, create.encode()), ok);
+            assert_eq!(
+                as_holder(AssetsFunc::Create, create.encode()),
+                status(AssetsStatusCode::AssetExists)
+            );
+            let mint = |origin| (origin, 1 as AssetId, holder.clone(), 100 as Balance).encode();
+            assert_eq!(
+                as_holder(AssetsFunc::Mint, mint(AssetsOrigin::Caller)),
+                status(AssetsStatusCode::NoPermission)
+            );
+            assert_eq!(as_holder(AssetsFunc::Mint, mint(AssetsOrigin::Address)), ok);
+            assert_eq!(
+                as_holder(AssetsFunc::TotalSupply, (1 as AssetId).encode()),
+                Ok(Ok((100 as Balance).encode()))
+            );
+
+            let transfer = |amount: Balance| {
+                (AssetsOrigin::Caller, 1 as AssetId, spender.clone(), amount).encode()
+            };
+            assert_eq!(
+                as_holder(AssetsFunc::Transfer, transfer(200)),
+                status(AssetsStatusCode::BalanceLow)
+            );
+            assert_eq!(as_holder(AssetsFunc::Transfer, transfer(30)), ok);
+            assert_eq!(
+                as_holder(AssetsFunc::BalanceOf, (1 as AssetId, spender.clone()).encode()),
+                Ok(Ok((30 as Balance).encode()))
+            );
+
+            // A second approval replaces the first one.
+            let approve = |amount: Balance| {
+                (AssetsOrigin::Caller, 1 as AssetId, spender.clone(), amount).encode()
+            };
+            let allowance = (1 as AssetId, holder.clone(), spender.clone()).encode();
+            assert_eq!(as_holder(AssetsFunc::Approve, approve(20)), ok);
+            assert_eq!(as_holder(AssetsFunc::Approve, approve(25)), ok);
+            assert_eq!(
+                as_holder(AssetsFunc::Allowance, allowance.clone()),
+                Ok(Ok((25 as Balance).encode()))
+            );
+            let transfer_approved = |amount: Balance| {
+                (AssetsOrigin::Caller, 1 as AssetId, holder.clone(), contract.clone(), amount)
+                    .encode()
+            };
+            assert_eq!(
+                as_spender(AssetsFunc::TransferApproved, transfer_approved(26)),
+                status(AssetsStatusCode::Unapproved)
+            );
+            assert_eq!(as_spender(AssetsFunc::TransferApproved, transfer_approved(15)), ok);
+            assert_eq!(
+                as_holder(AssetsFunc::Allowance, allowance.clone()),
+                Ok(Ok((10 as Balance).encode()))
+            );
+            assert_eq!(as_holder(AssetsFunc::Approve, approve(0)), ok);
+            assert_eq!(
+                as_holder(AssetsFunc::Allowance, allowance),
+                Ok(Ok((0 as Balance).encode()))
+            );
+
+            let burn = (AssetsOrigin::Address, 1 as AssetId, holder.clone(), 55 as Balance);
+            assert_eq!(as_holder(AssetsFunc::Burn, burn.encode()), ok);
+            assert_eq!(Assets::balance(1, &holder), 0);
+            assert_eq!(Assets::total_supply(1), 45);
+        });
+    }
+
+    #[test]
+    fn assets_metadata_is_written_as_a_tuple() {
+        new_test_ext().execute_with(|| {
+            let (contract, dispatch) = funded_contract(None);
+            let metadata = |id: AssetId| dispatch(AssetsFunc::Metadata, id.encode());
+            assert_eq!(metadata(1), Ok(Ok((Vec::<u8>::new(), Vec::<u8>::new(), 0u8).encode())));
+
+            let origin = Origin::signed(contract.clone());
+            assert_ok!(Assets::create(origin.clone(), 1, contract.clone().into(), 1));
+            assert_ok!(Assets::set_metadata(
+                origin,
+                1,
+                b"Shiden Dollar".to_vec(),
+                b"SDN$".to_vec(),
+                12
+            ));
+            assert_eq!(
+                metadata(1),
+                Ok(Ok((b"Shiden Dollar".to_vec(), b"SDN$".to_vec(), 12u8).encode()))
+            );
+        });
+    }
+}
@@ -0,0 +1,63 @@
+//! The Local Network EVM precompiles. This can be compiled with `#[no_std]`, ready for Wasm.
+
+use pallet_evm::{Context, Precompile, PrecompileResult, PrecompileSet};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_sha3fips::Sha3FIPS256;
+use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use sp_core::H160;
+use sp_std::marker::PhantomData;
+
+/// The PrecompileSet installed in the Local runtime.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalNetworkPrecompiles<R>(PhantomData<R>);
+
+impl<R> LocalNetworkPrecompiles<R> {
+    pub fn new() -> Self {
+        Self(Default::default())
+    }
+
+    /// Return all addresses that contain precompiles. This can be used to populate dummy code
+    /// under the precompile.
+    pub fn used_addresses() -> impl Iterator<Item = H160> {
+        sp_std::vec![1, 2, 3, 4, 5, 1024, 1026].into_iter().map(hash)
+    }
+}
+
+/// The following distribution has been decided for the precompiles
+/// 0-1023: Ethereum Mainnet Precompiles
+/// 1024-2047 Precompiles that are not in Ethereum Mainnet
+impl<R> PrecompileSet for LocalNetworkPrecompiles<R> {
+    fn execute(
+        &self,
+        address: H160,
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+        is_static: bool,
+    ) -> Option<PrecompileResult> {
+        match address {
+            // Ethereum precompiles :
+            a if a == hash(1) => Some(ECRecover::execute(input, target_gas, context, is_static)),
+            a if a == hash(2) => Some(Sha256::execute(input, target_gas, context, is_static)),
+            a if a == hash(3) => Some(Ripemd160::execute(input, target_gas, context, is_static)),
+            a if a == hash(4) => Some(Identity::execute(input, target_gas, context, is_static)),
+            a if a == hash(5) => Some(Modexp::execute(input, target_gas, context, is_static)),
+            // Non-Frontier specific nor Ethereum precompiles :
+            a if a == hash(1024) => {
+                Some(Sha3FIPS256::execute(input, target_gas, context, is_static))
+            }
+            a if a == hash(1026) => {
+                Some(ECRecoverPublicKey::execute(input, target_gas, context, is_static))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_precompile(&self, address: H160) -> bool {
+        Self::used_addresses().any(|x| x == address)
+    }
+}
+
+fn hash(a: u64) -> H160 {
+    H160::from_low_u64_be(a)
+}
@@ -0,0 +1,198 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Function IDs and argument types shared by the `ds` contract and `LocalChainExtension`.
+
+use scale::{Decode, Encode};
+
+/// First ID of the dapps staking range, `DappsStakingFunc` IDs follow it.
+pub const DAPPS_STAKING_EXTENSION: u32 = 3400;
+
+/// Functions of `pallet_dapps_staking` exposed through the chain extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DappsStakingFunc {
+    /// `current_era()`
+    CurrentEra = 3401,
+    /// `general_era_info(era)`
+    GeneralEraInfo = 3402,
+    /// `bond_and_stake(origin, contract_id, value)`
+    BondAndStake = 3403,
+    /// `unbond_and_unstake(origin, contract_id, value)`
+    UnbondAndUnstake = 3404,
+    /// `withdraw_unbonded(origin)`
+    WithdrawUnbonded = 3405,
+    /// `claim_staker(origin, contract_id)`
+    ClaimStaker = 3406,
+    /// `claim_dapp(origin, contract_id, era)`
+    ClaimDapp = 3407,
+    /// `staker_info(account, contract_id)`, latest staked value
+    StakedAmount = 3408,
+    /// `contract_stake_info(contract_id, era)`
+    ContractStake = 3409,
+    /// `ledger(account)`, unlocking chunks
+    UnbondingInfo = 3410,
+    /// `register(origin, contract_id)`
+    Register = 3411,
+}
+
+impl DappsStakingFunc {
+    /// Every declared function, in ID order.
+    pub const ALL: &'static [DappsStakingFunc] = &[
+        DappsStakingFunc::CurrentEra,
+        DappsStakingFunc::GeneralEraInfo,
+        DappsStakingFunc::BondAndStake,
+        DappsStakingFunc::UnbondAndUnstake,
+        DappsStakingFunc::WithdrawUnbonded,
+        DappsStakingFunc::ClaimStaker,
+        DappsStakingFunc::ClaimDapp,
+        DappsStakingFunc::StakedAmount,
+        DappsStakingFunc::ContractStake,
+        DappsStakingFunc::UnbondingInfo,
+        DappsStakingFunc::Register,
+    ];
+}
+
+impl From<DappsStakingFunc> for u32 {
+    fn from(func: DappsStakingFunc) -> Self {
+        func as u32
+    }
+}
+
+impl TryFrom<u32> for DappsStakingFunc {
+    /// The unknown `func_id`.
+    type Error = u32;
+
+    fn try_from(func_id: u32) -> Result<Self, Self::Error> {
+        DappsStakingFunc::ALL
+            .iter()
+            .copied()
+            .find(|func| *func as u32 == func_id)
+            .ok_or(func_id)
+    }
+}
+
+/// Status codes returned by `LocalChainExtension` through `RetVal::Converging`.
+///
+/// A non-zero code reports an expected failure and leaves the output buffer empty.
+/// Anything else the runtime cannot handle, such as an unknown `func_id` or an
+/// undecodable input, traps the calling contract instead.
+///
+/// | code | meaning                                                        |
+/// |------|----------------------------------------------------------------|
+/// | 0    | success, the output buffer holds the SCALE encoded result      |
+/// | 1    | the pallet call failed for a reason without a dedicated code   |
+/// | 2    | the requested era has no recorded info or reward               |
+/// | 3    | the contract is not registered for dapps staking               |
+/// | 4    | the contract is already registered                             |
+/// | 5    | the account has nothing staked on the contract                 |
+/// | 6    | there are no unbonded chunks ready to be withdrawn             |
+/// | 7    | the value is below the minimum or above the available balance  |
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum StatusCode {
+    Success = 0,
+    Failed = 1,
+    EraNotFound = 2,
+    NotRegistered = 3,
+    AlreadyRegistered = 4,
+    NotStaked = 5,
+    NothingToWithdraw = 6,
+    InsufficientValue = 7,
+}
+
+impl StatusCode {
+    /// Every declared status code, in code order.
+    pub const ALL: &'static [StatusCode] = &[
+        StatusCode::Success,
+        StatusCode::Failed,
+        StatusCode::EraNotFound,
+        StatusCode::NotRegistered,
+        StatusCode::AlreadyRegistered,
+        StatusCode::NotStaked,
+        StatusCode::NothingToWithdraw,
+        StatusCode::InsufficientValue,
+    ];
+}
+
+impl From<StatusCode> for u32 {
+    fn from(status: StatusCode) -> Self {
+        status as u32
+    }
+}
+
+impl TryFrom<u32> for StatusCode {
+    /// The unknown status code.
+    type Error = u32;
+
+    fn try_from(status_code: u32) -> Result<Self, Self::Error> {
+        StatusCode::ALL
+            .iter()
+            .copied()
+            .find(|status| *status as u32 == status_code)
+            .ok_or(status_code)
+    }
+}
+
+/// Account a dispatchable chain extension call is signed with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StakingOrigin {
+    /// The account that called the contract, only allowed to contracts in the
+    /// `CallerSigningContracts` of the runtime.
+    Caller,
+    /// The contract itself.
+    Address,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn func_ids_round_trip() {
+        for func in DappsStakingFunc::ALL {
+            assert!(u32::from(*func) > DAPPS_STAKING_EXTENSION);
+            assert_eq!(DappsStakingFunc::try_from(u32::from(*func)), Ok(*func));
+        }
+        assert_eq!(DappsStakingFunc::try_from(2001), Err(2001));
+    }
+
+    /// The function declared after `func`. The match is exhaustive, so a new variant
+    /// does not build until it is linked in here.
+    fn declared_after(func: DappsStakingFunc) -> Option<DappsStakingFunc> {
+        use DappsStakingFunc::*;
+        match func {
+            CurrentEra => Some(GeneralEraInfo),
+            GeneralEraInfo => Some(BondAndStake),
+            BondAndStake => Some(UnbondAndUnstake),
+            UnbondAndUnstake => Some(WithdrawUnbonded),
+            WithdrawUnbonded => Some(ClaimStaker),
+            ClaimStaker => Some(ClaimDapp),
+            ClaimDapp => Some(StakedAmount),
+            StakedAmount => Some(ContractStake),
+            ContractStake => Some(UnbondingInfo),
+            UnbondingInfo => Some(Register),
+            Register => None,
+        }
+    }
+
+    #[test]
+    fn all_lists_every_func_once_in_id_order() {
+        let declared: Vec<DappsStakingFunc> =
+            core::iter::successors(Some(DappsStakingFunc::CurrentEra), |func| declared_after(*func))
+                .collect();
+        assert_eq!(DappsStakingFunc::ALL, &declared[..]);
+        // Strictly increasing IDs also rule out a function listed twice.
+        assert!(DappsStakingFunc::ALL
+            .windows(2)
+            .all(|pair| u32::from(pair[0]) < u32::from(pair[1])));
+    }
+
+    #[test]
+    fn status_codes_round_trip() {
+        for status in StatusCode::ALL {
+            assert_eq!(StatusCode::try_from(u32::from(*status)), Ok(*status));
+        }
+        assert_eq!(StatusCode::try_from(99), Err(99));
+    }
+}
@@ -0,0 +1,653 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_env::{AccountId, Environment};
+use ink_lang as ink;
+use ink_prelude::{format, string::String, vec::Vec};
+use scale::{Decode, Encode};
+
+use assets_chain_extension_types::StatusCode;
+pub use assets_chain_extension_types::{AssetId, AssetsOrigin};
+
+type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+
+/// Chain extension for `pallet_assets`.
+///
+/// The IDs must match `assets_chain_extension_types::AssetsFunc`.
+#[ink::chain_extension]
+pub trait AssetsExt {
+    type ErrorCode = AssetsErrorCode;
+
+    /// `AssetsFunc::Create`, `admin` also becomes the issuer
+    #[ink(extension = 3601, returns_result = false)]
+    fn create(origin: AssetsOrigin, id: AssetId, admin: AccountId, min_balance: Balance);
+
+    /// `AssetsFunc::Mint`, signed by the issuer
+    #[ink(extension = 3602, returns_result = false)]
+    fn mint(origin: AssetsOrigin, id: AssetId, beneficiary: AccountId, amount: Balance);
+
+    /// `AssetsFunc::Burn`, signed by the admin
+    #[ink(extension = 3603, returns_result = false)]
+    fn burn(origin: AssetsOrigin, id: AssetId, who: AccountId, amount: Balance);
+
+    /// `AssetsFunc::Transfer`
+    #[ink(extension = 3604, returns_result = false)]
+    fn transfer(origin: AssetsOrigin, id: AssetId, target: AccountId, amount: Balance);
+
+    /// `AssetsFunc::Approve`, replaces the approval of `delegate`
+    #[ink(extension = 3605, returns_result = false)]
+    fn approve(origin: AssetsOrigin, id: AssetId, delegate: AccountId, amount: Balance);
+
+    /// `AssetsFunc::TransferApproved`, signed by the delegate
+    #[ink(extension = 3606, returns_result = false)]
+    fn transfer_approved(
+        origin: AssetsOrigin,
+        id: AssetId,
+        owner: AccountId,
+        destination: AccountId,
+        amount: Balance,
+    );
+
+    /// `AssetsFunc::BalanceOf`
+    #[ink(extension = 3607, returns_result = false)]
+    fn balance_of(id: AssetId, who: AccountId) -> Balance;
+
+    /// `AssetsFunc::TotalSupply`
+    #[ink(extension = 3608, returns_result = false)]
+    fn total_supply(id: AssetId) -> Balance;
+
+    /// `AssetsFunc::Metadata`, empty for an asset without metadata
+    #[ink(extension = 3609, returns_result = false)]
+    fn metadata(id: AssetId) -> AssetMetadata;
+
+    /// `AssetsFunc::Allowance`
+    #[ink(extension = 3610, returns_result = false)]
+    fn allowance(id: AssetId, owner: AccountId, delegate: AccountId) -> Balance;
+}
+
+/// Metadata of an asset as written by the runtime.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AssetMetadata {
+    pub name: Vec<u8>,
+    pub symbol: Vec<u8>,
+    pub decimals: u8,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AssetsErrorCode {
+    /// The runtime failed to execute the call
+    Failed,
+    /// The runtime returned a status code this contract does not know about
+    UnknownStatus(u32),
+    /// The asset does not exist
+    AssetUnknown,
+    /// The asset ID is already taken
+    AssetExists,
+    /// The signer is not the owner, admin or issuer of the asset
+    NoPermission,
+    /// The account does not hold enough of the asset
+    BalanceLow,
+    /// The balance would fall below the minimum balance of the asset
+    BelowMinimum,
+    /// The approval does not cover the amount
+    Unapproved,
+    /// The asset or the account is frozen
+    Frozen,
+}
+
+impl ink_env::chain_extension::FromStatusCode for AssetsErrorCode {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match StatusCode::try_from(status_code) {
+            Ok(StatusCode::Success) => Ok(()),
+            Ok(StatusCode::Failed) => Err(Self::Failed),
+            Ok(StatusCode::AssetUnknown) => Err(Self::AssetUnknown),
+            Ok(StatusCode::AssetExists) => Err(Self::AssetExists),
+            Ok(StatusCode::NoPermission) => Err(Self::NoPermission),
+            Ok(StatusCode::BalanceLow) => Err(Self::BalanceLow),
+            Ok(StatusCode::BelowMinimum) => Err(Self::BelowMinimum),
+            Ok(StatusCode::Unapproved) => Err(Self::Unapproved),
+            Ok(StatusCode::Frozen) => Err(Self::Frozen),
+            Err(unknown) => Err(Self::UnknownStatus(unknown)),
+        }
+    }
+}
+
+/// Errors of the openbrush PSP22 standard, encoded the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    /// Any other failure, e.g. of the runtime
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+impl From<AssetsErrorCode> for PSP22Error {
+    fn from(error_code: AssetsErrorCode) -> Self {
+        match error_code {
+            AssetsErrorCode::BalanceLow => Self::InsufficientBalance,
+            AssetsErrorCode::Unapproved => Self::InsufficientAllowance,
+            other => Self::Custom(format!("{:?}", other)),
+        }
+    }
+}
+
+/// The PSP22 messages, with the selectors of openbrush `PSP22`.
+#[ink::trait_definition]
+pub trait PSP22 {
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> Balance;
+
+    /// Amount `spender` may still transfer from `owner`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Sets the allowance of `spender` over the tokens of the caller to `value`.
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn increase_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: Balance,
+    ) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn decrease_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: Balance,
+    ) -> Result<(), PSP22Error>;
+}
+
+/// The PSP22 metadata extension, with the selectors of openbrush `PSP22Metadata`.
+#[ink::trait_definition]
+pub trait PSP22Metadata {
+    #[ink(message)]
+    fn token_name(&self) -> Option<String>;
+
+    #[ink(message)]
+    fn token_symbol(&self) -> Option<String>;
+
+    #[ink(message)]
+    fn token_decimals(&self) -> u8;
+}
+
+/// The PSP22 mintable extension, with the selectors of openbrush `PSP22Mintable`.
+#[ink::trait_definition]
+pub trait PSP22Mintable {
+    #[ink(message)]
+    fn mint(&mut self, account: AccountId, amount: Balance) -> Result<(), PSP22Error>;
+}
+
+/// The PSP22 burnable extension, with the selectors of openbrush `PSP22Burnable`.
+#[ink::trait_definition]
+pub trait PSP22Burnable {
+    #[ink(message)]
+    fn burn(&mut self, account: AccountId, amount: Balance) -> Result<(), PSP22Error>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+    const MAX_EVENT_TOPICS: usize = <ink_env::DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <ink_env::DefaultEnvironment as Environment>::AccountId;
+    type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+    type Hash = <ink_env::DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <ink_env::DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <ink_env::DefaultEnvironment as Environment>::Timestamp;
+
+    type ChainExtension = AssetsExt;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
+mod psp22_assets {
+    use super::{
+        AssetId, AssetsOrigin, PSP22Burnable, PSP22Error, PSP22Metadata, PSP22Mintable, PSP22,
+    };
+    use ink_prelude::{string::String, vec::Vec};
+
+    /// PSP22 view of one `pallet_assets` asset.
+    ///
+    /// Nothing is kept in the contract, every message is answered by the assets pallet.
+    /// Transfers and approvals are signed by the caller, so the balances are the
+    /// native ones and stay usable outside of the contract. The runtime only allows
+    /// that to code in its `CallerSigningContracts`, so governance must add the code
+    /// hash of this contract before transfers and approvals work. Minting and burning
+    /// are signed by the contract, which must be the issuer and admin of the asset,
+    /// and are limited to the account that instantiated it.
+    #[ink(storage)]
+    pub struct Psp22Assets {
+        asset_id: AssetId,
+        owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        value: Balance,
+    }
+
+    impl Psp22Assets {
+        /// Wraps the existing asset `asset_id`.
+        #[ink(constructor)]
+        pub fn new(asset_id: AssetId) -> Self {
+            Self {
+                asset_id,
+                owner: Self::env().caller(),
+            }
+        }
+
+        /// Creates the asset `asset_id`, with the contract as its owner, admin and issuer.
+        ///
+        /// The contract reserves the asset deposit, so it must be endowed with it.
+        #[ink(constructor, payable)]
+        pub fn create(asset_id: AssetId, min_balance: Balance) -> Self {
+            let contract = Self::env().account_id();
+            if let Err(error) = Self::env().extension().create(
+                AssetsOrigin::Address,
+                asset_id,
+                contract,
+                min_balance,
+            ) {
+                panic!("failed to create asset {}: {:?}", asset_id, error)
+            }
+            Self::new(asset_id)
+        }
+
+        #[ink(message)]
+        pub fn asset_id(&self) -> AssetId {
+            self.asset_id
+        }
+
+        fn ensure_owner(&self) -> Result<(), PSP22Error> {
+            if self.env().caller() != self.owner {
+                return Err(PSP22Error::Custom(String::from("Caller is not the owner")))
+            }
+            Ok(())
+        }
+
+        /// Token metadata field as a string, `None` if unset or not UTF-8.
+        fn metadata_string(&self, field: fn(super::AssetMetadata) -> Vec<u8>) -> Option<String> {
+            let metadata = self.env().extension().metadata(self.asset_id).ok()?;
+            Some(field(metadata))
+                .filter(|value| !value.is_empty())
+                .and_then(|value| String::from_utf8(value).ok())
+        }
+    }
+
+    impl PSP22 for Psp22Assets {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.env()
+                .extension()
+                .total_supply(self.asset_id)
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.env()
+                .extension()
+                .balance_of(self.asset_id, owner)
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.env()
+                .extension()
+                .allowance(self.asset_id, owner, spender)
+                .unwrap_or_default()
+        }
+
+        /// Transfers `value` of the caller to `to`, signed by the caller in the assets pallet.
+        ///
+        /// `data` is ignored, there is no receiver check.
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.env()
+                .extension()
+                .transfer(AssetsOrigin::Caller, self.asset_id, to, value)?;
+            self.env().emit_event(Transfer {
+                from: Some(self.env().caller()),
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` of `from` to `to`, spending the allowance of the caller.
+        ///
+        /// `data` is ignored, there is no receiver check.
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.env().extension().transfer_approved(
+                AssetsOrigin::Caller,
+                self.asset_id,
+                from,
+                to,
+                value,
+            )?;
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.env()
+                .extension()
+                .approve(AssetsOrigin::Caller, self.asset_id, spender, value)?;
+            self.env().emit_event(Approval {
+                owner: self.env().caller(),
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let allowance = self.allowance(self.env().caller(), spender);
+            self.approve(spender, allowance.saturating_add(delta_value))
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let allowance = self.allowance(self.env().caller(), spender);
+            let value = allowance
+                .checked_sub(delta_value)
+                .ok_or(PSP22Error::InsufficientAllowance)?;
+            self.approve(spender, value)
+        }
+    }
+
+    impl PSP22Metadata for Psp22Assets {
+        #[ink(message)]
+        fn token_name(&self) -> Option<String> {
+            self.metadata_string(|metadata| metadata.name)
+        }
+
+        #[ink(message)]
+        fn token_symbol(&self) -> Option<String> {
+            self.metadata_string(|metadata| metadata.symbol)
+        }
+
+        #[ink(message)]
+        fn token_decimals(&self) -> u8 {
+            self.env()
+                .extension()
+                .metadata(self.asset_id)
+                .map_or(0, |metadata| metadata.decimals)
+        }
+    }
+
+    impl PSP22Mintable for Psp22Assets {
+        /// Mints `amount` to `account`, only for the owner of the contract.
+        #[ink(message)]
+        fn mint(&mut self, account: AccountId, amount: Balance) -> Result<(), PSP22Error> {
+            self.ensure_owner()?;
+            self.env()
+                .extension()
+                .mint(AssetsOrigin::Address, self.asset_id, account, amount)?;
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(account),
+                value: amount,
+            });
+            Ok(())
+        }
+    }
+
+    impl PSP22Burnable for Psp22Assets {
+        /// Burns up to `amount` of `account`, only for the owner of the contract.
+        ///
+        /// The pallet burns the dust below the minimum balance as well, the `Transfer`
+        /// event reports the amount actually burned.
+        #[ink(message)]
+        fn burn(&mut self, account: AccountId, amount: Balance) -> Result<(), PSP22Error> {
+            self.ensure_owner()?;
+            let before = self.env().extension().balance_of(self.asset_id, account)?;
+            self.env()
+                .extension()
+                .burn(AssetsOrigin::Address, self.asset_id, account, amount)?;
+            let after = self.env().extension().balance_of(self.asset_id, account)?;
+            self.env().emit_event(Transfer {
+                from: Some(account),
+                to: None,
+                value: before.saturating_sub(after),
+            });
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::AssetsErrorCode;
+        use assets_chain_extension_types::{AssetsFunc, StatusCode};
+        use chain_extension_mock::{assets::Asset, MockRuntime};
+
+        type Event = <Psp22Assets as ::ink_lang::reflect::ContractEventBase>::Type;
+        use ink_env::chain_extension::FromStatusCode;
+
+        /// Imports `ink_lang` so we can use `#[ink::test]`.
+        use ink_lang as ink;
+
+        /// Asset 1, issued by the contract, with 100 minted to the caller.
+        fn mock_asset() -> MockRuntime {
+            let runtime = MockRuntime::new();
+            let origins = runtime.origins();
+            let mut assets = runtime.assets();
+            assets.assets.insert(
+                1,
+                Asset {
+                    supply: 100,
+                    name: b"Shiden Dollar".to_vec(),
+                    symbol: b"SDN$".to_vec(),
+                    decimals: 12,
+                    ..Asset::new(origins.address, 10)
+                },
+            );
+            assets.balances.insert((1, origins.caller), 100);
+            drop(assets);
+            runtime
+        }
+
+        #[test]
+        fn status_codes_convert_without_panicking() {
+            assert_eq!(AssetsErrorCode::from_status_code(0), Ok(()));
+            assert_eq!(
+                AssetsErrorCode::from_status_code(5),
+                Err(AssetsErrorCode::BalanceLow)
+            );
+            assert_eq!(
+                AssetsErrorCode::from_status_code(42),
+                Err(AssetsErrorCode::UnknownStatus(42))
+            );
+        }
+
+        #[ink::test]
+        fn transfer_moves_the_caller_balance() {
+            let runtime = mock_asset();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut psp22 = Psp22Assets::new(1);
+
+            assert_eq!(psp22.asset_id(), 1);
+            assert_eq!(psp22.total_supply(), 100);
+            assert_eq!(psp22.transfer(accounts.bob, 30, Vec::new()), Ok(()));
+            assert_eq!(psp22.balance_of(accounts.alice), 70);
+            assert_eq!(psp22.balance_of(accounts.bob), 30);
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+
+            assert_eq!(
+                psp22.transfer(accounts.bob, 71, Vec::new()),
+                Err(PSP22Error::InsufficientBalance)
+            );
+            assert_eq!(
+                psp22.transfer(accounts.charlie, 5, Vec::new()),
+                Err(PSP22Error::Custom(String::from("BelowMinimum")))
+            );
+            assert_eq!(runtime.assets().balance(1, accounts.alice), 70);
+        }
+
+        #[ink::test]
+        fn transfer_from_spends_the_allowance() {
+            let runtime = mock_asset();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut psp22 = Psp22Assets::new(1);
+
+            assert_eq!(psp22.approve(accounts.bob, 20), Ok(()));
+            assert_eq!(psp22.increase_allowance(accounts.bob, 30), Ok(()));
+            assert_eq!(psp22.decrease_allowance(accounts.bob, 10), Ok(()));
+            assert_eq!(
+                psp22.decrease_allowance(accounts.bob, 50),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(psp22.allowance(accounts.alice, accounts.bob), 40);
+
+            runtime.set_caller(accounts.bob);
+            assert_eq!(
+                psp22.transfer_from(accounts.alice, accounts.charlie, 41, Vec::new()),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(
+                psp22.transfer_from(accounts.alice, accounts.charlie, 25, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(psp22.allowance(accounts.alice, accounts.bob), 15);
+            assert_eq!(psp22.balance_of(accounts.alice), 75);
+            assert_eq!(psp22.balance_of(accounts.charlie), 25);
+        }
+
+        #[ink::test]
+        fn mint_and_burn_are_limited_to_the_owner() {
+            let runtime = mock_asset();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut psp22 = Psp22Assets::new(1);
+
+            assert_eq!(psp22.mint(accounts.bob, 50), Ok(()));
+            assert_eq!(psp22.burn(accounts.alice, 95), Ok(()));
+            // The dust below the minimum balance is burned as well, and reported.
+            assert_eq!(psp22.balance_of(accounts.alice), 0);
+            assert_eq!(psp22.total_supply(), 50);
+            let burned = ink_env::test::recorded_events()
+                .last()
+                .expect("burn emits an event");
+            match <Event as scale::Decode>::decode(&mut &burned.data[..]) {
+                Ok(Event::Transfer(transfer)) => assert_eq!(
+                    (transfer.from, transfer.to, transfer.value),
+                    (Some(accounts.alice), None, 100)
+                ),
+                _ => panic!("burn must emit a `Transfer` event"),
+            }
+
+            runtime.set_caller(accounts.bob);
+            assert_eq!(
+                psp22.mint(accounts.bob, 50),
+                Err(PSP22Error::Custom(String::from("Caller is not the owner")))
+            );
+            assert_eq!(
+                psp22.burn(accounts.bob, 50),
+                Err(PSP22Error::Custom(String::from("Caller is not the owner")))
+            );
+
+            // Only the issuer of the asset may mint, whoever owns the contract.
+            runtime.assets().assets.get_mut(&1).unwrap().issuer = accounts.django;
+            runtime.set_caller(accounts.alice);
+            assert_eq!(
+                psp22.mint(accounts.bob, 50),
+                Err(PSP22Error::Custom(String::from("NoPermission")))
+            );
+        }
+
+        #[ink::test]
+        fn create_makes_the_contract_issuer() {
+            let runtime = MockRuntime::new();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut psp22 = Psp22Assets::create(7, 1);
+
+            let contract = runtime.origins().address;
+            assert_eq!(runtime.assets().assets[&7].issuer, contract);
+            assert_eq!(psp22.mint(accounts.bob, 5), Ok(()));
+            assert_eq!(psp22.balance_of(accounts.bob), 5);
+            runtime.fail_next(AssetsFunc::Mint, StatusCode::Frozen);
+            assert_eq!(
+                psp22.mint(accounts.bob, 5),
+                Err(PSP22Error::Custom(String::from("Frozen")))
+            );
+        }
+
+        #[ink::test]
+        fn metadata_comes_from_the_asset() {
+            let _runtime = mock_asset();
+            let psp22 = Psp22Assets::new(1);
+            assert_eq!(psp22.token_name(), Some(String::from("Shiden Dollar")));
+            assert_eq!(psp22.token_symbol(), Some(String::from("SDN$")));
+            assert_eq!(psp22.token_decimals(), 12);
+
+            let unknown = Psp22Assets::new(2);
+            assert_eq!(unknown.token_name(), None);
+            assert_eq!(unknown.token_decimals(), 0);
+            assert_eq!(unknown.total_supply(), 0);
+        }
+    }
+}
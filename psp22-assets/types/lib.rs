@@ -0,0 +1,201 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Function IDs and argument types shared by `psp22_assets` and `LocalChainExtension`.
+
+use scale::{Decode, Encode};
+
+/// First ID of the assets range, `AssetsFunc` IDs follow it.
+pub const ASSETS_EXTENSION: u32 = 3600;
+
+/// `AssetId` of `pallet_assets` in the runtime.
+pub type AssetId = u128;
+
+/// Functions of `pallet_assets` exposed through the chain extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AssetsFunc {
+    /// `create(origin, id, admin, min_balance)`
+    Create = 3601,
+    /// `mint(origin, id, beneficiary, amount)`
+    Mint = 3602,
+    /// `burn(origin, id, who, amount)`
+    Burn = 3603,
+    /// `transfer(origin, id, target, amount)`
+    Transfer = 3604,
+    /// `approve_transfer(origin, id, delegate, amount)`, setting rather than adding to the approval
+    Approve = 3605,
+    /// `transfer_approved(origin, id, owner, destination, amount)`
+    TransferApproved = 3606,
+    /// `balance(id, who)`
+    BalanceOf = 3607,
+    /// `total_supply(id)`
+    TotalSupply = 3608,
+    /// `metadata(id)`, name, symbol and decimals
+    Metadata = 3609,
+    /// `allowance(id, owner, delegate)`
+    Allowance = 3610,
+}
+
+impl AssetsFunc {
+    /// Every declared function, in ID order.
+    pub const ALL: &'static [AssetsFunc] = &[
+        AssetsFunc::Create,
+        AssetsFunc::Mint,
+        AssetsFunc::Burn,
+        AssetsFunc::Transfer,
+        AssetsFunc::Approve,
+        AssetsFunc::TransferApproved,
+        AssetsFunc::BalanceOf,
+        AssetsFunc::TotalSupply,
+        AssetsFunc::Metadata,
+        AssetsFunc::Allowance,
+    ];
+}
+
+impl From<AssetsFunc> for u32 {
+    fn from(func: AssetsFunc) -> Self {
+        func as u32
+    }
+}
+
+impl TryFrom<u32> for AssetsFunc {
+    /// The unknown `func_id`.
+    type Error = u32;
+
+    fn try_from(func_id: u32) -> Result<Self, Self::Error> {
+        AssetsFunc::ALL
+            .iter()
+            .copied()
+            .find(|func| *func as u32 == func_id)
+            .ok_or(func_id)
+    }
+}
+
+/// Status codes returned by `LocalChainExtension` for the `AssetsFunc` IDs.
+///
+/// A non-zero code reports an expected failure and leaves the output buffer empty.
+///
+/// | code | meaning                                                        |
+/// |------|----------------------------------------------------------------|
+/// | 0    | success, the output buffer holds the SCALE encoded result      |
+/// | 1    | the pallet call failed for a reason without a dedicated code   |
+/// | 2    | the asset does not exist                                       |
+/// | 3    | the asset ID is already taken                                  |
+/// | 4    | the signer is not the owner, admin or issuer of the asset      |
+/// | 5    | the account does not hold enough of the asset                  |
+/// | 6    | the balance would fall below the minimum balance of the asset  |
+/// | 7    | the approval does not cover the amount                         |
+/// | 8    | the asset or the account is frozen                             |
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum StatusCode {
+    Success = 0,
+    Failed = 1,
+    AssetUnknown = 2,
+    AssetExists = 3,
+    NoPermission = 4,
+    BalanceLow = 5,
+    BelowMinimum = 6,
+    Unapproved = 7,
+    Frozen = 8,
+}
+
+impl StatusCode {
+    /// Every declared status code, in code order.
+    pub const ALL: &'static [StatusCode] = &[
+        StatusCode::Success,
+        StatusCode::Failed,
+        StatusCode::AssetUnknown,
+        StatusCode::AssetExists,
+        StatusCode::NoPermission,
+        StatusCode::BalanceLow,
+        StatusCode::BelowMinimum,
+        StatusCode::Unapproved,
+        StatusCode::Frozen,
+    ];
+}
+
+impl From<StatusCode> for u32 {
+    fn from(status: StatusCode) -> Self {
+        status as u32
+    }
+}
+
+impl TryFrom<u32> for StatusCode {
+    /// The unknown status code.
+    type Error = u32;
+
+    fn try_from(status_code: u32) -> Result<Self, Self::Error> {
+        StatusCode::ALL
+            .iter()
+            .copied()
+            .find(|status| *status as u32 == status_code)
+            .ok_or(status_code)
+    }
+}
+
+/// Account a dispatchable assets call is signed with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AssetsOrigin {
+    /// The account that called the contract, only allowed to contracts in the
+    /// `CallerSigningContracts` of the runtime.
+    Caller,
+    /// The contract itself.
+    Address,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn func_ids_round_trip() {
+        for func in AssetsFunc::ALL {
+            assert!(u32::from(*func) > ASSETS_EXTENSION);
+            assert_eq!(AssetsFunc::try_from(u32::from(*func)), Ok(*func));
+        }
+        assert_eq!(
+            AssetsFunc::try_from(ASSETS_EXTENSION),
+            Err(ASSETS_EXTENSION)
+        );
+    }
+
+    /// The function declared after `func`. The match is exhaustive, so a new variant
+    /// does not build until it is linked in here.
+    fn declared_after(func: AssetsFunc) -> Option<AssetsFunc> {
+        use AssetsFunc::*;
+        match func {
+            Create => Some(Mint),
+            Mint => Some(Burn),
+            Burn => Some(Transfer),
+            Transfer => Some(Approve),
+            Approve => Some(TransferApproved),
+            TransferApproved => Some(BalanceOf),
+            BalanceOf => Some(TotalSupply),
+            TotalSupply => Some(Metadata),
+            Metadata => Some(Allowance),
+            Allowance => None,
+        }
+    }
+
+    #[test]
+    fn all_lists_every_func_once_in_id_order() {
+        let declared: Vec<AssetsFunc> =
+            core::iter::successors(Some(AssetsFunc::Create), |func| declared_after(*func))
+                .collect();
+        assert_eq!(AssetsFunc::ALL, &declared[..]);
+        // Strictly increasing IDs also rule out a function listed twice.
+        assert!(AssetsFunc::ALL
+            .windows(2)
+            .all(|pair| u32::from(pair[0]) < u32::from(pair[1])));
+    }
+
+    #[test]
+    fn status_codes_round_trip() {
+        for status in StatusCode::ALL {
+            assert_eq!(StatusCode::try_from(u32::from(*status)), Ok(*status));
+        }
+        assert_eq!(StatusCode::try_from(99), Err(99));
+    }
+}
@@ -0,0 +1,284 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_env::Environment;
+use ink_lang as ink;
+
+type Hash = <ink_env::DefaultEnvironment as Environment>::Hash;
+type BlockNumber = <ink_env::DefaultEnvironment as Environment>::BlockNumber;
+
+/// Chain extension for `pallet_randomness_collective_flip`.
+#[ink::chain_extension]
+pub trait RandomnessExt {
+    type ErrorCode = RandomnessErrorCode;
+
+    /// Returns the random seed for `subject` and the block number since which it is known.
+    #[ink(extension = 1101, returns_result = false)]
+    fn random(subject: [u8; 32]) -> (Hash, BlockNumber);
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RandomnessErrorCode {
+    /// The runtime failed to execute the call
+    Failed,
+    /// The runtime returned a status code this contract does not know about
+    UnknownStatus(u32),
+    /// The runtime output could not be decoded
+    DecodeFailed,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RandomnessError {
+    ErrorCode(RandomnessErrorCode),
+    /// The caller has no pending commitment
+    NoCommitment,
+    /// The revealed secret does not match the commitment
+    CommitmentMismatch,
+    /// The seed of the target block of the commitment is not known yet
+    TooEarly,
+    /// The reveal window of the commitment has passed, commit again
+    Expired,
+}
+
+impl From<RandomnessErrorCode> for RandomnessError {
+    fn from(error_code: RandomnessErrorCode) -> Self {
+        Self::ErrorCode(error_code)
+    }
+}
+
+impl From<scale::Error> for RandomnessError {
+    fn from(_: scale::Error) -> Self {
+        Self::ErrorCode(RandomnessErrorCode::DecodeFailed)
+    }
+}
+
+impl ink_env::chain_extension::FromStatusCode for RandomnessErrorCode {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            1 => Err(Self::Failed),
+            _ => Err(Self::UnknownStatus(status_code)),
+        }
+    }
+}
+
+/// Blocks from a commitment to its target block, the first whose seed may decide it.
+pub const REVEAL_DELAY: BlockNumber = 1;
+
+/// Blocks, from the target block on, whose seeds may decide a commitment.
+///
+/// The player sees each of these seeds before revealing, so this bounds the number of
+/// outcomes they can choose from.
+pub const REVEAL_WINDOW: BlockNumber = 4;
+
+/// The commitment to `secret` expected by `Randomness::commit`.
+///
+/// Compute it off-chain, only the commitment may be submitted before the reveal.
+pub fn commitment_of(secret: &[u8; 32]) -> Hash {
+    let mut commitment = [0u8; 32];
+    ink_env::hash_bytes::<ink_env::hash::Blake2x256>(secret, &mut commitment);
+    commitment.into()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+    const MAX_EVENT_TOPICS: usize = <ink_env::DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <ink_env::DefaultEnvironment as Environment>::AccountId;
+    type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+    type Hash = <ink_env::DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <ink_env::DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <ink_env::DefaultEnvironment as Environment>::Timestamp;
+
+    type ChainExtension = RandomnessExt;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
+mod randomness_extension {
+    use super::{commitment_of, RandomnessError, REVEAL_DELAY, REVEAL_WINDOW};
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+
+    /// Random seeds from the runtime, with a commit-reveal scheme on top.
+    ///
+    /// A plain seed is known to block authors, and to everyone else once the block
+    /// is out. With commit-reveal a player first commits to a secret, and the
+    /// outcome mixes that secret with a seed which only became known after the
+    /// commitment was stored.
+    ///
+    /// The seed must be known since the target block, `REVEAL_DELAY` blocks after the
+    /// commitment, or one of the `REVEAL_WINDOW - 1` blocks after it. A commitment not
+    /// revealed by then expires.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct Randomness {
+        /// Pending commitment of an account and its target block
+        commitments: Mapping<AccountId, (Hash, BlockNumber)>,
+    }
+
+    #[ink(event)]
+    pub struct Committed {
+        #[ink(topic)]
+        account: AccountId,
+        commitment: Hash,
+        target_block: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct Revealed {
+        #[ink(topic)]
+        account: AccountId,
+        outcome: Hash,
+    }
+
+    impl Randomness {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            ink_lang::utils::initialize_contract(|_: &mut Self| {})
+        }
+
+        /// Calls random() in the pallet-randomness-collective-flip
+        #[ink(message)]
+        pub fn random(&self, subject: [u8; 32]) -> Result<(Hash, BlockNumber), RandomnessError> {
+            Ok(self.env().extension().random(subject)?)
+        }
+
+        /// Stores `commitment_of(secret)` for the caller, replacing a pending one.
+        #[ink(message)]
+        pub fn commit(&mut self, commitment: Hash) -> Result<(), RandomnessError> {
+            let account = self.env().caller();
+            let target_block = self.env().block_number().saturating_add(REVEAL_DELAY);
+            self.commitments.insert(account, &(commitment, target_block));
+            self.env().emit_event(Committed {
+                account,
+                commitment,
+                target_block,
+            });
+            Ok(())
+        }
+
+        /// Reveals the caller's secret and returns the outcome of its commitment.
+        ///
+        /// Fails with `TooEarly` while the runtime seed is known since before the target
+        /// block, retry in a later block. Fails with `Expired` once the seed is known since
+        /// a block past the reveal window.
+        #[ink(message)]
+        pub fn reveal(&mut self, secret: [u8; 32]) -> Result<Hash, RandomnessError> {
+            let account = self.env().caller();
+            let (commitment, target_block) = self
+                .commitments
+                .get(account)
+                .ok_or(RandomnessError::NoCommitment)?;
+            if commitment_of(&secret) != commitment {
+                return Err(RandomnessError::CommitmentMismatch)
+            }
+
+            let (seed, known_since) = self.env().extension().random(secret)?;
+            if known_since < target_block {
+                return Err(RandomnessError::TooEarly)
+            }
+            if known_since >= target_block.saturating_add(REVEAL_WINDOW) {
+                return Err(RandomnessError::Expired)
+            }
+
+            let mut input = [0u8; 64];
+            input[..32].copy_from_slice(seed.as_ref());
+            input[32..].copy_from_slice(&secret);
+            let mut outcome = [0u8; 32];
+            ink_env::hash_bytes::<ink_env::hash::Blake2x256>(&input, &mut outcome);
+            let outcome = Hash::from(outcome);
+
+            self.commitments.remove(account);
+            self.env().emit_event(Revealed { account, outcome });
+            Ok(outcome)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        /// Answers `random` with a fixed seed known since `known_since`.
+        struct MockedExtension {
+            known_since: BlockNumber,
+        }
+
+        impl ink_env::test::ChainExtension for MockedExtension {
+            fn func_id(&self) -> u32 {
+                1101
+            }
+
+            fn call(&mut self, _input: &[u8], output: &mut Vec<u8>) -> u32 {
+                let ret: (Hash, BlockNumber) = (Hash::from([7; 32]), self.known_since);
+                scale::Encode::encode_to(&ret, output);
+                0
+            }
+        }
+
+        #[ink::test]
+        fn random_works() {
+            ink_env::test::register_chain_extension(MockedExtension { known_since: 3 });
+            let randomness = Randomness::new();
+
+            assert_eq!(randomness.random([1; 32]), Ok((Hash::from([7; 32]), 3)));
+        }
+
+        #[ink::test]
+        fn commit_reveal_works() {
+            ink_env::test::register_chain_extension(MockedExtension { known_since: 1 });
+            let mut randomness = Randomness::new();
+            let secret = [42; 32];
+
+            assert_eq!(randomness.reveal(secret), Err(RandomnessError::NoCommitment));
+            randomness
+                .commit(commitment_of(&secret))
+                .expect("commit must work");
+            assert_eq!(
+                randomness.reveal([43; 32]),
+                Err(RandomnessError::CommitmentMismatch)
+            );
+
+            let outcome = randomness.reveal(secret).expect("reveal must work");
+            assert_ne!(outcome, Hash::from([7; 32]));
+            // The commitment is consumed by the reveal.
+            assert_eq!(randomness.reveal(secret), Err(RandomnessError::NoCommitment));
+        }
+
+        #[ink::test]
+        fn reveal_with_a_known_seed_is_too_early() {
+            ink_env::test::register_chain_extension(MockedExtension { known_since: 0 });
+            let mut randomness = Randomness::new();
+            let secret = [42; 32];
+
+            randomness
+                .commit(commitment_of(&secret))
+                .expect("commit must work");
+            assert_eq!(randomness.reveal(secret), Err(RandomnessError::TooEarly));
+        }
+
+        #[ink::test]
+        fn reveal_after_the_window_is_expired() {
+            let target_block = REVEAL_DELAY;
+            let mut randomness = Randomness::new();
+            let secret = [42; 32];
+
+            randomness
+                .commit(commitment_of(&secret))
+                .expect("commit must work");
+            ink_env::test::register_chain_extension(MockedExtension {
+                known_since: target_block + REVEAL_WINDOW,
+            });
+            assert_eq!(randomness.reveal(secret), Err(RandomnessError::Expired));
+
+            // The last seed of the window still decides the commitment.
+            ink_env::test::register_chain_extension(MockedExtension {
+                known_since: target_block + REVEAL_WINDOW - 1,
+            });
+            assert!(randomness.reveal(secret).is_ok());
+        }
+    }
+}
@@ -0,0 +1,615 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_env::{AccountId, Environment};
+use ink_lang as ink;
+use ink_prelude::{format, string::String, vec::Vec};
+use rmrk_chain_test::{RmrkErrorCode, RmrkExtension};
+use scale::{Decode, Encode};
+
+type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+
+/// Token ID, encoded the same way as the openbrush PSP34 `Id`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+impl Id {
+    /// The ID of an RMRK NFT, `collection_id` in the high and `nft_id` in the low 32 bits.
+    pub const fn from_nft(collection_id: u32, nft_id: u32) -> Self {
+        Self::U64((collection_id as u64) << 32 | nft_id as u64)
+    }
+
+    /// `(collection_id, nft_id)` of an ID built by `from_nft`.
+    pub const fn to_nft(&self) -> Option<(u32, u32)> {
+        match self {
+            Self::U64(id) => Some(((*id >> 32) as u32, *id as u32)),
+            _ => None,
+        }
+    }
+}
+
+/// Errors of the openbrush PSP34 standard, encoded the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    /// Any other failure, e.g. of the runtime
+    Custom(String),
+    SelfApprove,
+    /// The caller may not transfer the token
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+impl From<RmrkErrorCode> for PSP34Error {
+    fn from(error_code: RmrkErrorCode) -> Self {
+        match error_code {
+            RmrkErrorCode::NftUnknown => Self::TokenNotExists,
+            RmrkErrorCode::NoPermission => Self::NotApproved,
+            other => Self::Custom(format!("{:?}", other)),
+        }
+    }
+}
+
+/// The PSP34 messages, with the selectors of openbrush `PSP34`.
+#[ink::trait_definition]
+pub trait PSP34 {
+    /// ID of the collection, `Id::U32` of the RMRK collection.
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    /// Number of tokens owned by `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    /// Account owning the token, `None` if it does not exist or is nested in another NFT.
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    #[ink(message)]
+    fn approve(&mut self, operator: AccountId, id: Option<Id>, approved: bool) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    /// Number of tokens in the collection.
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+}
+
+/// The PSP34 metadata extension, with the selectors of openbrush `PSP34Metadata`.
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    /// Attribute `key` of a token, or of the collection for `PSP34::collection_id`.
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CustomEnvironment {}
+
+impl Environment for CustomEnvironment {
+    const MAX_EVENT_TOPICS: usize = <ink_env::DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <ink_env::DefaultEnvironment as Environment>::AccountId;
+    type Balance = <ink_env::DefaultEnvironment as Environment>::Balance;
+    type Hash = <ink_env::DefaultEnvironment as Environment>::Hash;
+    type BlockNumber = <ink_env::DefaultEnvironment as Environment>::BlockNumber;
+    type Timestamp = <ink_env::DefaultEnvironment as Environment>::Timestamp;
+
+    type ChainExtension = RmrkExtension;
+}
+
+#[ink::contract(env = crate::CustomEnvironment)]
+mod rmrk_psp34 {
+    use super::{Id, PSP34Error, PSP34Metadata, PSP34};
+    use ink_prelude::vec::Vec;
+    use ink_storage::{traits::SpreadAllocate, Mapping};
+    use rmrk_chain_test::{AccountIdOrCollectionNftTuple, RMRK_PAGE_LIMIT};
+
+    /// PSP34 view of one RMRK collection.
+    ///
+    /// The tokens are the NFTs of the collection, identified by `Id::from_nft`.
+    /// Ownership is answered by the RMRK pallet, the contract only keeps approvals.
+    ///
+    /// RMRK has no approvals and a transfer is signed by the owner of the NFT, so an
+    /// approved token is held by the contract until it is transferred or the approval
+    /// is revoked:
+    /// - `approve` of a token moves it from its owner to the contract, revoking the
+    ///   approval moves it back. `owner_of` and `balance_of` keep reporting the owner.
+    /// - An approval for all tokens of an owner covers the tokens the contract holds
+    ///   for that owner, a token in the owner's own account cannot be moved by anyone
+    ///   else.
+    /// - `transfer` of a held token is signed by the contract, other transfers by the
+    ///   caller, which the runtime only allows once governance put the code hash of
+    ///   this contract in `CallerSigningContracts`.
+    #[ink(storage)]
+    #[derive(SpreadAllocate)]
+    pub struct RmrkPsp34 {
+        collection_id: u32,
+        /// Owner of a token held by the contract, by `nft_id`
+        held: Mapping<u32, AccountId>,
+        /// Number of tokens held for an owner
+        held_count: Mapping<AccountId, u32>,
+        /// Number of tokens held for all owners
+        held_total: u32,
+        /// Operator approved for a held token, by `nft_id`
+        token_approvals: Mapping<u32, AccountId>,
+        /// Operators approved for all tokens of an owner
+        operators: Mapping<(AccountId, AccountId), ()>,
+    }
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(topic)]
+        id: Option<Id>,
+        approved: bool,
+    }
+
+    impl RmrkPsp34 {
+        #[ink(constructor)]
+        pub fn new(collection_id: u32) -> Self {
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.collection_id = collection_id;
+            })
+        }
+
+        /// `nft_id` of a token of this collection.
+        fn nft_id(&self, id: &Id) -> Option<u32> {
+            match id.to_nft() {
+                Some((collection_id, nft_id)) if collection_id == self.collection_id => Some(nft_id),
+                _ => None,
+            }
+        }
+
+        /// Value of the RMRK property `key` of an NFT, or of the collection if `nft_id` is `None`.
+        fn property(&self, nft_id: Option<u32>, key: &[u8]) -> Option<Vec<u8>> {
+            let mut start = None;
+            loop {
+                let page = self
+                    .env()
+                    .extension()
+                    .properties(self.collection_id, nft_id, start, RMRK_PAGE_LIMIT)
+                    .ok()?;
+                let full = page.len() == RMRK_PAGE_LIMIT as usize;
+                start = page.last().map(|property| property.key.clone());
+                if let Some(property) = page.into_iter().find(|property| property.key == key) {
+                    return Some(property.value)
+                }
+                if !full {
+                    return None
+                }
+            }
+        }
+
+        /// Number of NFTs of the collection `owner` holds in the RMRK pallet.
+        fn rmrk_balance(&self, owner: AccountId) -> u32 {
+            let mut balance = 0;
+            let mut start = None;
+            loop {
+                let page = self.env().extension().balance_of(owner, self.collection_id, start);
+                let (count, last) = match page {
+                    Ok(page) => page,
+                    Err(_) => return balance,
+                };
+                balance += count;
+                if last.is_none() {
+                    return balance
+                }
+                start = last;
+            }
+        }
+
+        /// Moves a token of the caller to the contract, to hold it for `owner`.
+        fn hold(&mut self, owner: AccountId, nft_id: u32) -> Result<(), PSP34Error> {
+            self.env().extension().send_from_caller(
+                self.collection_id,
+                nft_id,
+                AccountIdOrCollectionNftTuple::AccountId(self.env().account_id()),
+            )?;
+            self.held.insert(nft_id, &owner);
+            self.held_count.insert(owner, &(self.held_count.get(owner).unwrap_or_default() + 1));
+            self.held_total += 1;
+            Ok(())
+        }
+
+        /// Sends a held token to `to`, signed by the contract, and drops its approval.
+        fn release(
+            &mut self,
+            owner: AccountId,
+            nft_id: u32,
+            to: AccountId,
+        ) -> Result<(), PSP34Error> {
+            self.env().extension().send(
+                self.collection_id,
+                nft_id,
+                AccountIdOrCollectionNftTuple::AccountId(to),
+            )?;
+            self.held.remove(nft_id);
+            self.token_approvals.remove(nft_id);
+            self.held_count.insert(owner, &(self.held_count.get(owner).unwrap_or_default() - 1));
+            self.held_total -= 1;
+            Ok(())
+        }
+    }
+
+    impl PSP34 for RmrkPsp34 {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::U32(self.collection_id)
+        }
+
+        /// Counts the tokens in the account of `owner` and those the contract holds for it.
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u32 {
+            let balance = self.rmrk_balance(owner);
+            if owner == self.env().account_id() {
+                balance.saturating_sub(self.held_total)
+            } else {
+                balance + self.held_count.get(owner).unwrap_or_default()
+            }
+        }
+
+        /// Account owning the token, the owner it is held for if the contract holds it.
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<AccountId> {
+            let nft_id = self.nft_id(&id)?;
+            let address = self.env().account_id();
+            match self.env().extension().nft(self.collection_id, nft_id).ok()??.owner {
+                AccountIdOrCollectionNftTuple::AccountId(owner) if owner == address => {
+                    self.held.get(nft_id).or(Some(owner))
+                }
+                AccountIdOrCollectionNftTuple::AccountId(owner) => Some(owner),
+                AccountIdOrCollectionNftTuple::CollectionAndNftTuple(..) => None,
+            }
+        }
+
+        /// Whether `operator` may transfer all tokens of `owner` held by the contract, or
+        /// the held token `id`.
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+            let for_all = self.operators.contains((owner, operator));
+            match id {
+                None => for_all,
+                Some(id) => match self.nft_id(&id) {
+                    Some(nft_id) if self.held.get(nft_id) == Some(owner) => {
+                        for_all || self.token_approvals.get(nft_id) == Some(operator)
+                    }
+                    _ => false,
+                },
+            }
+        }
+
+        /// Approves `operator` for the token `id` of the caller, or for all its tokens.
+        ///
+        /// Approving a token moves it to the contract, revoking it moves it back, see
+        /// `RmrkPsp34`.
+        #[ink(message)]
+        fn approve(
+            &mut self,
+            operator: AccountId,
+            id: Option<Id>,
+            approved: bool,
+        ) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(PSP34Error::SelfApprove)
+            }
+            match &id {
+                None if approved => self.operators.insert((caller, operator), &()),
+                None => self.operators.remove((caller, operator)),
+                Some(id) => {
+                    let nft_id = self.nft_id(id).ok_or(PSP34Error::TokenNotExists)?;
+                    match self.owner_of(id.clone()) {
+                        None => return Err(PSP34Error::TokenNotExists),
+                        Some(owner) if owner != caller => return Err(PSP34Error::NotApproved),
+                        Some(_) => {}
+                    }
+                    let is_held = self.held.contains(nft_id);
+                    if approved {
+                        if !is_held {
+                            self.hold(caller, nft_id)?;
+                        }
+                        self.token_approvals.insert(nft_id, &operator);
+                    } else if is_held {
+                        self.release(caller, nft_id, caller)?;
+                    }
+                }
+            }
+            self.env().emit_event(Approval {
+                from: caller,
+                to: operator,
+                id,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Sends a token to `to`. A token held by the contract is sent by the contract for
+        /// its owner or an approved operator, any other token by its owner.
+        ///
+        /// `data` is ignored, there is no receiver check.
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            let nft_id = self.nft_id(&id).ok_or(PSP34Error::TokenNotExists)?;
+            let caller = self.env().caller();
+            let from = match self.held.get(nft_id) {
+                Some(owner) => {
+                    if owner != caller && !self.allowance(owner, caller, Some(id.clone())) {
+                        return Err(PSP34Error::NotApproved)
+                    }
+                    self.release(owner, nft_id, to)?;
+                    owner
+                }
+                None => {
+                    match self.owner_of(id.clone()) {
+                        None => return Err(PSP34Error::TokenNotExists),
+                        Some(owner) if owner != caller => return Err(PSP34Error::NotApproved),
+                        Some(_) => {}
+                    }
+                    self.env().extension().send_from_caller(
+                        self.collection_id,
+                        nft_id,
+                        AccountIdOrCollectionNftTuple::AccountId(to),
+                    )?;
+                    caller
+                }
+            };
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                id,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.env()
+                .extension()
+                .collection(self.collection_id)
+                .ok()
+                .flatten()
+                .map_or(0, |collection| collection.nfts_count as Balance)
+        }
+    }
+
+    impl PSP34Metadata for RmrkPsp34 {
+        /// `metadata` of the token or collection, `symbol` of the collection, or an RMRK property.
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            if id == Id::U32(self.collection_id) {
+                let collection = self.env().extension().collection(self.collection_id).ok()??;
+                return match &key[..] {
+                    b"metadata" => Some(collection.metadata),
+                    b"symbol" => Some(collection.symbol),
+                    _ => self.property(None, &key),
+                }
+            }
+            let nft_id = self.nft_id(&id)?;
+            match &key[..] {
+                b"metadata" => Some(self.env().extension().nft(self.collection_id, nft_id).ok()??.metadata),
+                _ => self.property(Some(nft_id), &key),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use chain_extension_mock::{
+            rmrk::{Collection, Nft, Owner},
+            MockRuntime,
+        };
+        use rmrk_chain_test::RMRK_BALANCE_LIMIT;
+
+        /// Imports `ink_lang` so we can use `#[ink::test]`.
+        use ink_lang as ink;
+
+        /// Collection 1 holds NFT 0 owned by the caller and NFT 1 nested in it.
+        fn mock_collection() -> MockRuntime {
+            let runtime = MockRuntime::new();
+            let caller = runtime.origins().caller;
+            let nft = |owner| Nft {
+                owner,
+                royalty: None,
+                metadata: b"ipfs://nft".to_vec(),
+                equipped: false,
+                pending: false,
+                transferable: true,
+            };
+            let mut rmrk = runtime.rmrk();
+            for nfts_count in [0, 2] {
+                rmrk.collections.push(Collection {
+                    issuer: caller,
+                    metadata: b"ipfs://collection".to_vec(),
+                    max: None,
+                    symbol: b"ROO".to_vec(),
+                    nfts_count,
+                });
+            }
+            rmrk.nfts.insert((1, 0), nft(Owner::AccountId(caller)));
+            rmrk.nfts.insert((1, 1), nft(Owner::CollectionAndNftTuple(1, 0)));
+            rmrk.properties.insert((1, None, b"color".to_vec()), b"blue".to_vec());
+            rmrk.properties.insert((1, Some(0), b"color".to_vec()), b"red".to_vec());
+            drop(rmrk);
+            runtime
+        }
+
+        #[ink::test]
+        fn balance_of_counts_past_a_page() {
+            let runtime = mock_collection();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let psp34 = RmrkPsp34::new(1);
+            let nft = runtime.rmrk().nfts[&(1, 0)].clone();
+            for nft_id in 2..2 * RMRK_BALANCE_LIMIT + 2 {
+                runtime.rmrk().nfts.insert((1, nft_id), nft.clone());
+            }
+
+            assert_eq!(psp34.balance_of(accounts.alice), 2 * RMRK_BALANCE_LIMIT + 1);
+        }
+
+        #[test]
+        fn id_maps_collection_and_nft() {
+            assert_eq!(Id::from_nft(1, 2), Id::U64(0x1_0000_0002));
+            assert_eq!(Id::from_nft(u32::MAX, 7).to_nft(), Some((u32::MAX, 7)));
+            assert_eq!(Id::U32(1).to_nft(), None);
+        }
+
+        #[ink::test]
+        fn owner_of_delegates_to_rmrk() {
+            let _runtime = mock_collection();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let psp34 = RmrkPsp34::new(1);
+
+            assert_eq!(psp34.collection_id(), Id::U32(1));
+            assert_eq!(psp34.owner_of(Id::from_nft(1, 0)), Some(accounts.alice));
+            // Nested in another NFT, unknown, or of another collection.
+            assert_eq!(psp34.owner_of(Id::from_nft(1, 1)), None);
+            assert_eq!(psp34.owner_of(Id::from_nft(1, 2)), None);
+            assert_eq!(psp34.owner_of(Id::from_nft(0, 0)), None);
+            assert_eq!(psp34.balance_of(accounts.alice), 1);
+            assert_eq!(psp34.total_supply(), 2);
+        }
+
+        #[ink::test]
+        fn transfer_is_limited_to_the_owner() {
+            let runtime = mock_collection();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut psp34 = RmrkPsp34::new(1);
+
+            runtime.set_caller(accounts.bob);
+            assert_eq!(
+                psp34.transfer(accounts.bob, Id::from_nft(1, 0), Vec::new()),
+                Err(PSP34Error::NotApproved)
+            );
+            runtime.set_caller(accounts.alice);
+            assert_eq!(
+                psp34.transfer(accounts.bob, Id::from_nft(1, 2), Vec::new()),
+                Err(PSP34Error::TokenNotExists)
+            );
+            assert_eq!(psp34.transfer(accounts.bob, Id::from_nft(1, 0), Vec::new()), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+            assert_eq!(psp34.owner_of(Id::from_nft(1, 0)), Some(accounts.bob));
+            assert_eq!(psp34.balance_of(accounts.alice), 0);
+
+        }
+
+        #[ink::test]
+        fn approved_token_is_held_until_transferred() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.django);
+            let runtime = mock_collection();
+            let mut psp34 = RmrkPsp34::new(1);
+            let token = Id::from_nft(1, 0);
+
+            assert_eq!(
+                psp34.approve(accounts.alice, Some(token.clone()), true),
+                Err(PSP34Error::SelfApprove)
+            );
+            runtime.set_caller(accounts.bob);
+            assert_eq!(
+                psp34.approve(accounts.charlie, Some(token.clone()), true),
+                Err(PSP34Error::NotApproved)
+            );
+            runtime.set_caller(accounts.alice);
+            assert_eq!(psp34.approve(accounts.bob, Some(token.clone()), true), Ok(()));
+            assert!(psp34.allowance(accounts.alice, accounts.bob, Some(token.clone())));
+            assert!(!psp34.allowance(accounts.alice, accounts.charlie, Some(token.clone())));
+            // Held by the contract, still reported as the token of its owner.
+            assert_eq!(
+                runtime.rmrk().nfts[&(1, 0)].owner,
+                Owner::AccountId(accounts.django)
+            );
+            assert_eq!(psp34.owner_of(token.clone()), Some(accounts.alice));
+            assert_eq!(psp34.balance_of(accounts.alice), 1);
+            assert_eq!(psp34.balance_of(accounts.django), 0);
+
+            runtime.set_caller(accounts.charlie);
+            assert_eq!(
+                psp34.transfer(accounts.charlie, token.clone(), Vec::new()),
+                Err(PSP34Error::NotApproved)
+            );
+            runtime.set_caller(accounts.bob);
+            assert_eq!(psp34.transfer(accounts.charlie, token.clone(), Vec::new()), Ok(()));
+            assert_eq!(psp34.owner_of(token.clone()), Some(accounts.charlie));
+            assert_eq!(psp34.balance_of(accounts.alice), 0);
+            assert_eq!(psp34.balance_of(accounts.charlie), 1);
+            assert!(!psp34.allowance(accounts.alice, accounts.bob, Some(token)));
+        }
+
+        #[ink::test]
+        fn revoked_token_is_returned_and_operators_cover_held_tokens() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_callee::<ink_env::DefaultEnvironment>(accounts.django);
+            let runtime = mock_collection();
+            let mut psp34 = RmrkPsp34::new(1);
+            let token = Id::from_nft(1, 0);
+
+            assert_eq!(psp34.approve(accounts.bob, Some(token.clone()), true), Ok(()));
+            assert_eq!(psp34.approve(accounts.bob, Some(token.clone()), false), Ok(()));
+            assert_eq!(runtime.rmrk().nfts[&(1, 0)].owner, Owner::AccountId(accounts.alice));
+            assert!(!psp34.allowance(accounts.alice, accounts.bob, Some(token.clone())));
+
+            // An operator for all tokens moves those the contract holds.
+            assert_eq!(psp34.approve(accounts.bob, None, true), Ok(()));
+            assert!(psp34.allowance(accounts.alice, accounts.bob, None));
+            runtime.set_caller(accounts.bob);
+            assert_eq!(
+                psp34.transfer(accounts.bob, token.clone(), Vec::new()),
+                Err(PSP34Error::NotApproved)
+            );
+            runtime.set_caller(accounts.alice);
+            assert_eq!(psp34.approve(accounts.charlie, Some(token.clone()), true), Ok(()));
+            assert!(psp34.allowance(accounts.alice, accounts.bob, Some(token.clone())));
+            assert_eq!(psp34.approve(accounts.bob, None, false), Ok(()));
+            assert!(!psp34.allowance(accounts.alice, accounts.bob, Some(token.clone())));
+
+            // The owner still transfers a held token itself.
+            assert_eq!(psp34.transfer(accounts.eve, token.clone(), Vec::new()), Ok(()));
+            assert_eq!(psp34.owner_of(token), Some(accounts.eve));
+            assert_eq!(ink_env::test::recorded_events().count(), 6);
+        }
+
+        #[ink::test]
+        fn attributes_come_from_rmrk() {
+            let _runtime = mock_collection();
+            let psp34 = RmrkPsp34::new(1);
+            let attribute = |id, key: &[u8]| psp34.get_attribute(id, key.to_vec());
+
+            assert_eq!(attribute(Id::U32(1), b"symbol"), Some(b"ROO".to_vec()));
+            assert_eq!(attribute(Id::U32(1), b"metadata"), Some(b"ipfs://collection".to_vec()));
+            assert_eq!(attribute(Id::U32(1), b"color"), Some(b"blue".to_vec()));
+            assert_eq!(attribute(Id::from_nft(1, 0), b"metadata"), Some(b"ipfs://nft".to_vec()));
+            assert_eq!(attribute(Id::from_nft(1, 0), b"color"), Some(b"red".to_vec()));
+            assert_eq!(attribute(Id::from_nft(1, 0), b"size"), None);
+            assert_eq!(attribute(Id::from_nft(1, 2), b"metadata"), None);
+        }
+    }
+}
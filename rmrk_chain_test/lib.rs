@@ -3,52 +3,450 @@
 use ink_env::{AccountId, Environment};
 use ink_lang as ink;
 use ink_prelude::vec::Vec;
+#[cfg(feature = "std")]
+use ink_storage::traits::StorageLayout;
+use ink_storage::traits::{PackedLayout, SpreadLayout};
 
 use scale::{Decode, Encode};
-// use rmrk_chain_extension_types::RmrkFunc;
-// const RMRK_EXTENSION: u32 = 3500;
+use rmrk_chain_extension_types::StatusCode;
+pub use rmrk_chain_extension_types::{RMRK_BALANCE_LIMIT, RMRK_PAGE_LIMIT};
 
+/// Details of a collection, decoded from `rmrk_traits::CollectionInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CollectionInfo {
+    pub issuer: AccountId,
+    pub metadata: Vec<u8>,
+    /// Maximum number of NFTs, unlimited if `None`
+    pub max: Option<u32>,
+    pub symbol: Vec<u8>,
+    /// Number of NFTs currently in the collection
+    pub nfts_count: u32,
+}
+
+/// Royalty of an NFT, decoded from `rmrk_traits::RoyaltyInfo`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RoyaltyInfo {
+    pub recipient: AccountId,
+    pub amount: Permill,
+}
+
+/// Details of an NFT, decoded from `rmrk_traits::NftInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NftInfo {
+    pub owner: AccountIdOrCollectionNftTuple,
+    pub royalty: Option<RoyaltyInfo>,
+    pub metadata: Vec<u8>,
+    /// Whether the NFT is equipped into its parent
+    pub equipped: bool,
+    /// Whether the NFT waits to be accepted by the owner of its parent
+    pub pending: bool,
+    pub transferable: bool,
+}
+
+/// A property of a collection or an NFT.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Property {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+/// Resources added to an NFT on mint.
+pub type ResourcesOnMint = Option<Vec<ResourceTypes>>;
+
+/// A resource rendered on its own, encoded the same way as `rmrk_traits::BasicResource`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BasicResource {
+    /// URI of the media
+    pub src: Option<Vec<u8>>,
+    /// URI of the metadata
+    pub metadata: Option<Vec<u8>>,
+    pub license: Option<Vec<u8>>,
+    /// URI of a preview image
+    pub thumb: Option<Vec<u8>>,
+}
+
+/// A resource composed of the `parts` of a `base`, encoded the same way as
+/// `rmrk_traits::ComposableResource`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ComposableResource {
+    /// IDs of the base parts the NFT is rendered with
+    pub parts: Vec<u32>,
+    pub base: u32,
+    pub src: Option<Vec<u8>>,
+    pub metadata: Option<Vec<u8>>,
+    pub license: Option<Vec<u8>>,
+    pub thumb: Option<Vec<u8>>,
+}
+
+/// A resource equippable into `slot` of a `base`, encoded the same way as
+/// `rmrk_traits::SlotResource`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SlotResource {
+    pub base: u32,
+    pub src: Option<Vec<u8>>,
+    pub metadata: Option<Vec<u8>>,
+    pub slot: u32,
+    pub license: Option<Vec<u8>>,
+    pub thumb: Option<Vec<u8>>,
+}
+
+/// Any RMRK resource, encoded the same way as `rmrk_traits::ResourceTypes`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ResourceTypes {
+    Basic(BasicResource),
+    Composable(ComposableResource),
+    Slot(SlotResource),
+}
+
+/// Collections whose NFTs may be equipped into a slot, encoded the same way as
+/// `rmrk_traits::EquippableList`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum EquippableList {
+    All,
+    Empty,
+    Custom(Vec<u32>),
+}
+
+/// A part always rendered with the base, encoded the same way as `rmrk_traits::FixedPart`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct FixedPart {
+    pub id: u32,
+    /// Rendering order, higher is on top
+    pub z: u32,
+    pub src: Vec<u8>,
+}
+
+/// A part NFTs can be equipped into, encoded the same way as `rmrk_traits::SlotPart`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct SlotPart {
+    pub id: u32,
+    pub equippable: EquippableList,
+    /// Fallback media while nothing is equipped
+    pub src: Vec<u8>,
+    pub z: u32,
+}
+
+/// A part of a base, encoded the same way as `rmrk_traits::PartType`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PartType {
+    FixedPart(FixedPart),
+    SlotPart(SlotPart),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ThemeProperty {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+/// Named set of properties for rendering a base, encoded the same way as `rmrk_traits::Theme`.
+///
+/// The first theme of a base must be named `default`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Theme {
+    pub name: Vec<u8>,
+    pub properties: Vec<ThemeProperty>,
+    /// Whether properties missing here fall back to the `default` theme
+    pub inherit: bool,
+}
+
+/// Owner of an NFT, encoded the same way as `rmrk_traits::AccountIdOrCollectionNftTuple`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AccountIdOrCollectionNftTuple {
+    /// Owned by an account
+    AccountId(AccountId),
+    /// Nested in the NFT `(collection_id, nft_id)`
+    CollectionAndNftTuple(u32, u32),
+}
+
+/// Parts per million, encoded the same way as `sp_runtime::Permill`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Permill(u32);
+
+impl Permill {
+    /// Parts making up one whole.
+    pub const ACCURACY: u32 = 1_000_000;
+
+    /// `parts` per million, clamped to one whole.
+    pub const fn from_parts(parts: u32) -> Self {
+        if parts > Self::ACCURACY {
+            Self(Self::ACCURACY)
+        } else {
+            Self(parts)
+        }
+    }
+
+    /// `percent` per hundred, clamped to one whole.
+    pub const fn from_percent(percent: u8) -> Self {
+        if percent > 100 {
+            Self(Self::ACCURACY)
+        } else {
+            Self(percent as u32 * 10_000)
+        }
+    }
+
+    pub const fn deconstruct(self) -> u32 {
+        self.0
+    }
+
+    /// Whether the value lies between zero and one whole, which only a decoded value may not.
+    pub const fn is_valid(self) -> bool {
+        self.0 <= Self::ACCURACY
+    }
+}
+
+/// Chain extension for `pallet_rmrk_core`.
+///
+/// The IDs must match `rmrk_chain_extension_types::RmrkFunc`. Failures are reported
+/// through the status code, so the output holds the plain result.
 #[ink::chain_extension]
 pub trait RmrkExtension {
     type ErrorCode = RmrkErrorCode;
 
-    #[ink(extension = 3501)]
-    fn next_nft_id(collection_id: u32) -> Result<u32, RmrkError>;
+    /// `RmrkFunc::NextNftId`
+    #[ink(extension = 3501, returns_result = false)]
+    fn next_nft_id(collection_id: u32) -> u32;
 
-    #[ink(extension = 3502)]
-    fn collection_index() -> Result<u32, RmrkError>;
+    /// `RmrkFunc::CollectionIndex`
+    #[ink(extension = 3502, returns_result = false)]
+    fn collection_index() -> u32;
 
-    #[ink(extension = 3513)]
+    /// `RmrkFunc::CollectionInfo`
+    #[ink(extension = 3503, returns_result = false)]
+    fn collection(collection_id: u32) -> Option<CollectionInfo>;
+
+    /// `RmrkFunc::NftInfo`
+    #[ink(extension = 3504, returns_result = false)]
+    fn nft(collection_id: u32, nft_id: u32) -> Option<NftInfo>;
+
+    /// `RmrkFunc::Children`, up to `limit` children after `start`
+    #[ink(extension = 3506, returns_result = false)]
+    fn children(
+        collection_id: u32,
+        nft_id: u32,
+        start: Option<(u32, u32)>,
+        limit: u32,
+    ) -> Vec<(u32, u32)>;
+
+    /// `RmrkFunc::Properties`, up to `limit` properties after the key `start`
+    #[ink(extension = 3510, returns_result = false)]
+    fn properties(
+        collection_id: u32,
+        nft_id: Option<u32>,
+        start: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Vec<Property>;
+
+    /// `RmrkFunc::BalanceOf`, up to `RMRK_BALANCE_LIMIT` NFTs after the NFT ID `start`,
+    /// and the NFT ID to start the next page after
+    #[ink(extension = 3511, returns_result = false)]
+    fn balance_of(owner: AccountId, collection_id: u32, start: Option<u32>) -> (u32, Option<u32>);
+
+    /// `RmrkFunc::MintNft`
+    #[ink(extension = 3513, returns_result = false)]
+    #[allow(clippy::too_many_arguments)]
     fn mint_ntf(
         beneficiary: AccountId,
         collection_id: u32,
         royalty_recipient: Option<AccountId>,
-        royalty: Option<u8>,
+        royalty: Option<Permill>,
         metadata: Vec<u8>,
         transferable: bool,
-        resources: Option< ((Vec<u8>, Vec<u8>), u32) >,
-    ) -> Result<(), RmrkError>;
+        resources: ResourcesOnMint,
+    ) -> (u32, u32);
 
-    #[ink(extension = 3515)]
+    /// `RmrkFunc::CreateCollection`
+    #[ink(extension = 3515, returns_result = false)]
     fn create_collection(
         metadata: Vec<u8>,
         max: Option<u32>,
         symbol: Vec<u8>,
-    ) -> Result<(), RmrkError>;
+    ) -> u32;
+
+    /// `RmrkFunc::BurnNft`
+    #[ink(extension = 3516, returns_result = false)]
+    fn burn_nft(collection_id: u32, nft_id: u32);
+
+    /// `RmrkFunc::Send`
+    #[ink(extension = 3518, returns_result = false)]
+    fn send(collection_id: u32, nft_id: u32, new_owner: AccountIdOrCollectionNftTuple);
+
+    /// `RmrkFunc::AcceptNft`
+    #[ink(extension = 3519, returns_result = false)]
+    fn accept_nft(collection_id: u32, nft_id: u32, new_owner: AccountIdOrCollectionNftTuple);
+
+    /// `RmrkFunc::RejectNft`
+    #[ink(extension = 3520, returns_result = false)]
+    fn reject_nft(collection_id: u32, nft_id: u32);
+
+    /// `RmrkFunc::ChangeCollectionIssuer`
+    #[ink(extension = 3521, returns_result = false)]
+    fn change_collection_issuer(collection_id: u32, new_issuer: AccountId);
+
+    /// `RmrkFunc::AddBasicResource`
+    #[ink(extension = 3524, returns_result = false)]
+    fn add_basic_resource(collection_id: u32, nft_id: u32, resource: BasicResource);
+
+    /// `RmrkFunc::AddComposableResource`
+    #[ink(extension = 3525, returns_result = false)]
+    fn add_composable_resource(collection_id: u32, nft_id: u32, resource: ComposableResource);
+
+    /// `RmrkFunc::AddSlotResource`
+    #[ink(extension = 3526, returns_result = false)]
+    fn add_slot_resource(collection_id: u32, nft_id: u32, resource: SlotResource);
+
+    /// `RmrkFunc::AcceptResource`
+    #[ink(extension = 3527, returns_result = false)]
+    fn accept_resource(collection_id: u32, nft_id: u32, resource_id: u32);
+
+    /// `RmrkFunc::RemoveResource`
+    #[ink(extension = 3528, returns_result = false)]
+    fn remove_resource(collection_id: u32, nft_id: u32, resource_id: u32);
+
+    /// `RmrkFunc::AcceptResourceRemoval`
+    #[ink(extension = 3529, returns_result = false)]
+    fn accept_resource_removal(collection_id: u32, nft_id: u32, resource_id: u32);
+
+    /// `RmrkFunc::SetPriority`
+    #[ink(extension = 3530, returns_result = false)]
+    fn set_priority(collection_id: u32, nft_id: u32, priorities: Vec<u32>);
+
+    /// `RmrkFunc::CreateBase`
+    #[ink(extension = 3531, returns_result = false)]
+    fn create_base(base_type: Vec<u8>, symbol: Vec<u8>, parts: Vec<PartType>);
+
+    /// `RmrkFunc::ThemeAdd`
+    #[ink(extension = 3532, returns_result = false)]
+    fn theme_add(base_id: u32, theme: Theme);
+
+    /// `RmrkFunc::Equip`
+    #[ink(extension = 3533, returns_result = false)]
+    fn equip(item: (u32, u32), equipper: (u32, u32), resource_id: u32, base_id: u32, slot_id: u32);
+
+    /// `RmrkFunc::Unequip`
+    #[ink(extension = 3534, returns_result = false)]
+    fn unequip(item: (u32, u32), unequipper: (u32, u32), base_id: u32, slot_id: u32);
+
+    /// `RmrkFunc::Equippable`
+    #[ink(extension = 3535, returns_result = false)]
+    fn equippable(base_id: u32, slot_id: u32, equippables: EquippableList);
+
+    /// `RmrkFunc::ChangeBaseIssuer`
+    #[ink(extension = 3536, returns_result = false)]
+    fn change_base_issuer(base_id: u32, new_issuer: AccountId);
+
+    /// `RmrkFunc::SendFromCaller`, traps unless the runtime lets the contract sign with its
+    /// caller
+    #[ink(extension = 3537, returns_result = false)]
+    fn send_from_caller(collection_id: u32, nft_id: u32, new_owner: AccountIdOrCollectionNftTuple);
 }
 
 #[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum RmrkErrorCode {
+    /// The runtime failed to execute the call
     Failed,
+    /// The runtime returned a status code this contract does not know about
+    UnknownStatus(u32),
+    /// The runtime output could not be decoded
+    DecodeFailed,
+    /// The collection does not exist
+    CollectionUnknown,
+    /// The contract is not allowed to act on the collection or NFT
+    NoPermission,
+    /// The collection is full or locked
+    CollectionFullOrLocked,
+    /// The NFT is not transferable
+    NonTransferable,
+    /// Metadata, symbol or another bounded argument is too long
+    TooLong,
+    /// The NFT does not exist
+    NftUnknown,
+    /// The NFT is locked, e.g. while equipped
+    NftLocked,
+    /// The NFT cannot be sent to itself or one of its descendants
+    InvalidDestination,
+    /// The resource does not exist on the NFT
+    ResourceUnknown,
+    /// The resource is not pending acceptance or removal
+    ResourceNotPending,
+    /// The base does not exist
+    BaseUnknown,
+    /// The part does not exist in the base
+    PartUnknown,
+    /// The item cannot be equipped into that slot
+    NotEquippable,
+    /// The slot is already equipped
+    AlreadyEquipped,
+    /// The item is not equipped
+    NotEquipped,
+    /// The base needs a `default` theme before other themes
+    NeedsDefaultTheme,
+    /// The royalty is more than one whole
+    RoyaltyOutOfRange,
 }
 
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 #[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
 pub enum RmrkError {
     ErrorCode(RmrkErrorCode),
+    /// The royalty is more than one whole
+    RoyaltyOutOfRange,
+    /// The caller lacks the role
+    MissingRole(Role),
+    /// Only the contract owner may do this
+    NotOwner,
+    /// The collection was not created through this contract
+    UnknownCollection,
+    /// More items than `Rmrk::max_batch` in one call
+    BatchTooLarge,
+    /// Minting item `index` of an atomic batch failed, nothing was minted
+    BatchItemFailed { index: u32, error: RmrkErrorCode },
+    /// The batch size is zero or above `MAX_BATCH`
+    InvalidMaxBatch,
+}
+
+/// Roles granted by the `Rmrk` contract.
+///
+/// The contract owner holds every role, an admin is a minter as well.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, SpreadLayout, PackedLayout)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+pub enum Role {
+    /// Creates collections and bases, and manages the NFTs held by the contract
+    Admin,
+    /// Mints NFTs into the collections of the contract
+    Minter,
+}
+
+/// How `Rmrk::mint_batch` handles an item which fails to mint.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BatchMode {
+    /// Reverts the whole call, so either every item is minted or none
+    Atomic,
+    /// Keeps minting the remaining items and reports the result of each
+    PerItem,
 }
 
+/// `(collection_id, nft_id)` or the error of each item of `Rmrk::mint_batch`.
+pub type BatchResults = Vec<Result<(u32, u32), RmrkError>>;
+
 impl From<RmrkErrorCode> for RmrkError {
     fn from(error_code: RmrkErrorCode) -> Self {
         Self::ErrorCode(error_code)
@@ -57,16 +455,33 @@ impl From<RmrkErrorCode> for RmrkError {
 
 impl From<scale::Error> for RmrkError {
     fn from(_: scale::Error) -> Self {
-        panic!("encountered unexpected invalid SCALE encoding")
+        Self::ErrorCode(RmrkErrorCode::DecodeFailed)
     }
 }
 
 impl ink_env::chain_extension::FromStatusCode for RmrkErrorCode {
     fn from_status_code(status_code: u32) -> Result<(), Self> {
-        match status_code {
-            0 => Ok(()),
-            1 => Err(Self::Failed),
-            _ => panic!("encountered unknown status code"),
+        match StatusCode::try_from(status_code) {
+            Ok(StatusCode::Success) => Ok(()),
+            Ok(StatusCode::Failed) => Err(Self::Failed),
+            Ok(StatusCode::CollectionUnknown) => Err(Self::CollectionUnknown),
+            Ok(StatusCode::NoPermission) => Err(Self::NoPermission),
+            Ok(StatusCode::CollectionFullOrLocked) => Err(Self::CollectionFullOrLocked),
+            Ok(StatusCode::NonTransferable) => Err(Self::NonTransferable),
+            Ok(StatusCode::TooLong) => Err(Self::TooLong),
+            Ok(StatusCode::NftUnknown) => Err(Self::NftUnknown),
+            Ok(StatusCode::NftLocked) => Err(Self::NftLocked),
+            Ok(StatusCode::InvalidDestination) => Err(Self::InvalidDestination),
+            Ok(StatusCode::ResourceUnknown) => Err(Self::ResourceUnknown),
+            Ok(StatusCode::ResourceNotPending) => Err(Self::ResourceNotPending),
+            Ok(StatusCode::BaseUnknown) => Err(Self::BaseUnknown),
+            Ok(StatusCode::PartUnknown) => Err(Self::PartUnknown),
+            Ok(StatusCode::NotEquippable) => Err(Self::NotEquippable),
+            Ok(StatusCode::AlreadyEquipped) => Err(Self::AlreadyEquipped),
+            Ok(StatusCode::NotEquipped) => Err(Self::NotEquipped),
+            Ok(StatusCode::NeedsDefaultTheme) => Err(Self::NeedsDefaultTheme),
+            Ok(StatusCode::RoyaltyOutOfRange) => Err(Self::RoyaltyOutOfRange),
+            Err(unknown) => Err(Self::UnknownStatus(unknown)),
         }
     }
 }
@@ -90,17 +505,143 @@ impl Environment for CustomEnvironment {
 #[ink::contract(env = crate::CustomEnvironment)]
 mod rmrk_chain_test {
 
-    use super::RmrkError;
+    use super::{
+        AccountIdOrCollectionNftTuple,
+        BasicResource,
+        BatchMode,
+        BatchResults,
+        CollectionInfo,
+        ComposableResource,
+        EquippableList,
+        NftInfo,
+        PartType,
+        Permill,
+        Property,
+        ResourcesOnMint,
+        RmrkError,
+        Role,
+        RoyaltyInfo,
+        SlotResource,
+        Theme,
+    };
+    use ink_storage::{traits::SpreadAllocate, Mapping};
     use ink_prelude::vec::Vec;
 
+    /// `Rmrk::max_batch` until the owner changes it.
+    pub const DEFAULT_MAX_BATCH: u32 = 50;
+
+    /// Upper limit of `Rmrk::max_batch`, so that a full batch of `mint_ntf` calls stays
+    /// well within the gas a single call can spend.
+    pub const MAX_BATCH: u32 = 100;
+
+    /// Issues RMRK collections on behalf of its owner, admins and minters.
     #[ink(storage)]
-    pub struct Rmrk {}
+    #[derive(SpreadAllocate)]
+    pub struct Rmrk {
+        owner: AccountId,
+        /// Accounts holding a role, the owner is implied
+        roles: Mapping<(Role, AccountId), ()>,
+        /// Account which created a collection through this contract
+        collections: Mapping<u32, AccountId>,
+        /// Most items `mint_batch` accepts in one call
+        max_batch: u32,
+    }
+
+    #[ink(event)]
+    pub struct RoleGranted {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoked {
+        #[ink(topic)]
+        role: Role,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CollectionCreated {
+        #[ink(topic)]
+        collection_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct NftMinted {
+        #[ink(topic)]
+        collection_id: u32,
+        nft_id: u32,
+        #[ink(topic)]
+        owner: AccountId,
+    }
 
     impl Rmrk {
 
+        /// The caller becomes the owner of the contract.
         #[ink(constructor)]
         pub fn new() -> Self {
-            Rmrk {}
+            ink_lang::utils::initialize_contract(|contract: &mut Self| {
+                contract.owner = Self::env().caller();
+                contract.max_batch = DEFAULT_MAX_BATCH;
+            })
+        }
+
+        #[ink(message)]
+        pub fn contract_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Whether `account` holds `role`, directly or through a higher one.
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            account == self.owner
+                || self.roles.contains((role, account))
+                || (role == Role::Minter && self.roles.contains((Role::Admin, account)))
+        }
+
+        /// Grants `role` to `account`; admins are granted by the owner, minters by an admin.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<(), RmrkError> {
+            self.ensure_may_manage(role)?;
+            self.roles.insert((role, account), &());
+            self.env().emit_event(RoleGranted { role, account });
+            Ok(())
+        }
+
+        /// Revokes `role` from `account`, see `grant_role`.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<(), RmrkError> {
+            self.ensure_may_manage(role)?;
+            self.roles.remove((role, account));
+            self.env().emit_event(RoleRevoked { role, account });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn max_batch(&self) -> u32 {
+            self.max_batch
+        }
+
+        /// Sets the most items `mint_batch` accepts in one call, from 1 up to `MAX_BATCH`.
+        #[ink(message)]
+        pub fn set_max_batch(&mut self, max_batch: u32) -> Result<(), RmrkError> {
+            if self.env().caller() != self.owner {
+                return Err(RmrkError::NotOwner)
+            }
+            if max_batch == 0 || max_batch > MAX_BATCH {
+                return Err(RmrkError::InvalidMaxBatch)
+            }
+            self.max_batch = max_batch;
+            Ok(())
+        }
+
+        /// Account which created `collection_id` through this contract.
+        #[ink(message)]
+        pub fn collection_owner(&self, collection_id: u32) -> Option<AccountId> {
+            self.collections.get(collection_id)
         }
 
         #[ink(message)]
@@ -117,80 +658,789 @@ mod rmrk_chain_test {
             Ok(collection_id)
         }
 
+        /// Details of a collection, `None` if it does not exist.
+        #[ink(message)]
+        pub fn collection(&self, collection_id: u32) -> Result<Option<CollectionInfo>, RmrkError> {
+            Ok(self.env().extension().collection(collection_id)?)
+        }
+
+        /// Details of an NFT, `None` if it does not exist.
+        #[ink(message)]
+        pub fn nft(&self, collection_id: u32, nft_id: u32) -> Result<Option<NftInfo>, RmrkError> {
+            Ok(self.env().extension().nft(collection_id, nft_id)?)
+        }
+
+        /// Owner of an NFT, an account or the NFT it is nested in.
+        #[ink(message)]
+        pub fn owner(
+            &self,
+            collection_id: u32,
+            nft_id: u32,
+        ) -> Result<Option<AccountIdOrCollectionNftTuple>, RmrkError> {
+            Ok(self.nft(collection_id, nft_id)?.map(|nft| nft.owner))
+        }
+
+        /// `(collection_id, nft_id)` of the NFTs nested directly in an NFT.
+        ///
+        /// Returns a page of up to `limit` children, at most `RMRK_PAGE_LIMIT`, following
+        /// `start`. Pass the last child of a page as `start` to get the next one.
+        #[ink(message)]
+        pub fn children(
+            &self,
+            collection_id: u32,
+            nft_id: u32,
+            start: Option<(u32, u32)>,
+            limit: u32,
+        ) -> Result<Vec<(u32, u32)>, RmrkError> {
+            Ok(self
+                .env()
+                .extension()
+                .children(collection_id, nft_id, start, limit)?)
+        }
+
+        /// Properties of an NFT, or of the collection itself if `nft_id` is `None`.
+        ///
+        /// Returns a page of up to `limit` properties, at most `RMRK_PAGE_LIMIT`, following
+        /// the key `start`. Pass the last key of a page as `start` to get the next one.
+        #[ink(message)]
+        pub fn properties(
+            &self,
+            collection_id: u32,
+            nft_id: Option<u32>,
+            start: Option<Vec<u8>>,
+            limit: u32,
+        ) -> Result<Vec<Property>, RmrkError> {
+            Ok(self
+                .env()
+                .extension()
+                .properties(collection_id, nft_id, start, limit)?)
+        }
+
+        /// Mints an NFT into `collection_id`, paying `royalty` of each sale to `royalty_recipient`.
+        ///
+        /// Returns `(collection_id, nft_id)` of the new NFT.
         #[ink(message)]
+        #[allow(clippy::too_many_arguments)]
         pub fn mint_ntf(&mut self,
             beneficiary: AccountId,
             collection_id: u32,
             royalty_recipient: Option<AccountId>,
-            _royalty: Option<u8>,
+            royalty: Option<Permill>,
             metadata: Vec<u8>,
             transferable: bool,
-            resources: Option< ((Vec<u8>, Vec<u8>), u32) >,
-        ) -> Result<(), RmrkError>{
-            let _result = self
+            resources: ResourcesOnMint,
+        ) -> Result<(u32, u32), RmrkError>{
+            self.ensure_role(Role::Minter)?;
+            if !self.collections.contains(collection_id) {
+                return Err(RmrkError::UnknownCollection)
+            }
+            if royalty.is_some_and(|royalty| !royalty.is_valid()) {
+                return Err(RmrkError::RoyaltyOutOfRange)
+            }
+            let (collection_id, nft_id) = self
             .env()
             .extension()
             .mint_ntf(
                 beneficiary,
                 collection_id,
                 royalty_recipient,
-                None, // fix to use Permill
+                royalty,
                 metadata,
                 transferable,
                 resources,
             )?;
-            Ok(())
+            self.env().emit_event(NftMinted {
+                collection_id,
+                nft_id,
+                owner: beneficiary,
+            });
+            Ok((collection_id, nft_id))
+        }
+
+        /// Mints an NFT with `metadata` to each beneficiary of `items`, all sharing `royalty`.
+        ///
+        /// Returns the result of every item in order. With `BatchMode::Atomic` the first
+        /// failure reverts the call instead, reported as `BatchItemFailed`.
+        #[ink(message)]
+        pub fn mint_batch(
+            &mut self,
+            collection_id: u32,
+            items: Vec<(AccountId, Vec<u8>)>,
+            royalty: Option<RoyaltyInfo>,
+            transferable: bool,
+            mode: BatchMode,
+        ) -> Result<BatchResults, RmrkError> {
+            self.ensure_role(Role::Minter)?;
+            if !self.collections.contains(collection_id) {
+                return Err(RmrkError::UnknownCollection)
+            }
+            if royalty.is_some_and(|royalty| !royalty.amount.is_valid()) {
+                return Err(RmrkError::RoyaltyOutOfRange)
+            }
+            if items.len() > self.max_batch as usize {
+                return Err(RmrkError::BatchTooLarge)
+            }
+            let mut results = Vec::with_capacity(items.len());
+            for (index, (beneficiary, metadata)) in items.into_iter().enumerate() {
+                let minted = self.env().extension().mint_ntf(
+                    beneficiary,
+                    collection_id,
+                    royalty.map(|royalty| royalty.recipient),
+                    royalty.map(|royalty| royalty.amount),
+                    metadata,
+                    transferable,
+                    None,
+                );
+                match minted {
+                    Ok((collection_id, nft_id)) => self.env().emit_event(NftMinted {
+                        collection_id,
+                        nft_id,
+                        owner: beneficiary,
+                    }),
+                    Err(error) if mode == BatchMode::Atomic => {
+                        return Err(RmrkError::BatchItemFailed {
+                            index: index as u32,
+                            error,
+                        })
+                    }
+                    Err(_) => {}
+                }
+                results.push(minted.map_err(RmrkError::from));
+            }
+            Ok(results)
         }
 
+        /// Creates a collection issued by this contract and returns its ID.
+        ///
+        /// The calling admin becomes the owner of the collection.
         #[ink(message)]
         pub fn create_collection(
             &mut self,
             metadata: Vec<u8>,
             max: Option<u32>,
             symbol: Vec<u8>,
-        ) -> Result<(), RmrkError> {
-            let _result = self
+        ) -> Result<u32, RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            let collection_id = self
                 .env()
                 .extension()
                 .create_collection(metadata, max, symbol)?;
+            self.collections.insert(collection_id, &self.env().caller());
+            self.env().emit_event(CollectionCreated { collection_id });
+            Ok(collection_id)
+        }
+
+        /// Burns an NFT owned by this contract, together with the NFTs nested in it.
+        #[ink(message)]
+        pub fn burn_nft(&mut self, collection_id: u32, nft_id: u32) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env().extension().burn_nft(collection_id, nft_id)?;
+            Ok(())
+        }
+
+        /// Sends an NFT owned by this contract to an account, or nests it in another NFT.
+        ///
+        /// Sending into an NFT of another owner leaves it pending until that owner accepts it.
+        #[ink(message)]
+        pub fn send(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            new_owner: AccountIdOrCollectionNftTuple,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .send(collection_id, nft_id, new_owner)?;
+            Ok(())
+        }
+
+        /// Accepts an NFT pending to be nested in an NFT owned by this contract.
+        #[ink(message)]
+        pub fn accept_nft(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            new_owner: AccountIdOrCollectionNftTuple,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .accept_nft(collection_id, nft_id, new_owner)?;
+            Ok(())
+        }
+
+        /// Rejects, and thereby burns, an NFT pending to be nested in an NFT owned by this contract.
+        #[ink(message)]
+        pub fn reject_nft(&mut self, collection_id: u32, nft_id: u32) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env().extension().reject_nft(collection_id, nft_id)?;
+            Ok(())
+        }
+
+        /// Hands a collection issued by this contract over to `new_issuer`.
+        ///
+        /// The contract stops tracking the collection.
+        #[ink(message)]
+        pub fn change_collection_issuer(
+            &mut self,
+            collection_id: u32,
+            new_issuer: AccountId,
+        ) -> Result<(), RmrkError> {
+            self.ensure_may_manage_collection(collection_id)?;
+            self.env()
+                .extension()
+                .change_collection_issuer(collection_id, new_issuer)?;
+            self.collections.remove(collection_id);
+            Ok(())
+        }
+
+        /// Adds a basic resource to an NFT, pending until its owner accepts it.
+        ///
+        /// Resources added to an NFT owned by this contract are accepted right away.
+        #[ink(message)]
+        pub fn add_basic_resource(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            resource: BasicResource,
+        ) -> Result<(), RmrkError> {
+            self.ensure_may_manage_collection(collection_id)?;
+            self.env()
+                .extension()
+                .add_basic_resource(collection_id, nft_id, resource)?;
+            Ok(())
+        }
+
+        /// Adds a composable resource to an NFT, see `add_basic_resource`.
+        #[ink(message)]
+        pub fn add_composable_resource(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            resource: ComposableResource,
+        ) -> Result<(), RmrkError> {
+            self.ensure_may_manage_collection(collection_id)?;
+            self.env()
+                .extension()
+                .add_composable_resource(collection_id, nft_id, resource)?;
+            Ok(())
+        }
+
+        /// Adds a slot resource to an NFT, see `add_basic_resource`.
+        #[ink(message)]
+        pub fn add_slot_resource(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            resource: SlotResource,
+        ) -> Result<(), RmrkError> {
+            self.ensure_may_manage_collection(collection_id)?;
+            self.env()
+                .extension()
+                .add_slot_resource(collection_id, nft_id, resource)?;
+            Ok(())
+        }
+
+        /// Accepts a resource pending on an NFT owned by this contract.
+        #[ink(message)]
+        pub fn accept_resource(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            resource_id: u32,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .accept_resource(collection_id, nft_id, resource_id)?;
+            Ok(())
+        }
+
+        /// Removes a resource from an NFT in a collection issued by this contract.
+        ///
+        /// The removal is pending until the owner of the NFT accepts it.
+        #[ink(message)]
+        pub fn remove_resource(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            resource_id: u32,
+        ) -> Result<(), RmrkError> {
+            self.ensure_may_manage_collection(collection_id)?;
+            self.env()
+                .extension()
+                .remove_resource(collection_id, nft_id, resource_id)?;
+            Ok(())
+        }
+
+        /// Accepts the pending removal of a resource from an NFT owned by this contract.
+        #[ink(message)]
+        pub fn accept_resource_removal(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            resource_id: u32,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .accept_resource_removal(collection_id, nft_id, resource_id)?;
+            Ok(())
+        }
+
+        /// Orders the resources of an NFT owned by this contract, most preferred first.
+        #[ink(message)]
+        pub fn set_priority(
+            &mut self,
+            collection_id: u32,
+            nft_id: u32,
+            priorities: Vec<u32>,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .set_priority(collection_id, nft_id, priorities)?;
+            Ok(())
+        }
+
+        /// Creates a base issued by this contract, made of fixed and slot `parts`.
+        #[ink(message)]
+        pub fn create_base(
+            &mut self,
+            base_type: Vec<u8>,
+            symbol: Vec<u8>,
+            parts: Vec<PartType>,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .create_base(base_type, symbol, parts)?;
+            Ok(())
+        }
+
+        /// Adds a theme to a base issued by this contract, or replaces the one with the same name.
+        #[ink(message)]
+        pub fn theme_add(&mut self, base_id: u32, theme: Theme) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env().extension().theme_add(base_id, theme)?;
+            Ok(())
+        }
+
+        /// Equips the `item` NFT into `slot_id` of its direct parent `equipper`.
+        ///
+        /// `resource_id` is the composable resource of `equipper` rendered with `base_id`.
+        #[ink(message)]
+        pub fn equip(
+            &mut self,
+            item: (u32, u32),
+            equipper: (u32, u32),
+            resource_id: u32,
+            base_id: u32,
+            slot_id: u32,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .equip(item, equipper, resource_id, base_id, slot_id)?;
+            Ok(())
+        }
+
+        /// Unequips the `item` NFT from `slot_id` of `unequipper`.
+        #[ink(message)]
+        pub fn unequip(
+            &mut self,
+            item: (u32, u32),
+            unequipper: (u32, u32),
+            base_id: u32,
+            slot_id: u32,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .unequip(item, unequipper, base_id, slot_id)?;
+            Ok(())
+        }
+
+        /// Sets the collections allowed into a slot part of a base issued by this contract.
+        #[ink(message)]
+        pub fn equippable(
+            &mut self,
+            base_id: u32,
+            slot_id: u32,
+            equippables: EquippableList,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .equippable(base_id, slot_id, equippables)?;
+            Ok(())
+        }
+
+        /// Hands a base issued by this contract over to `new_issuer`.
+        #[ink(message)]
+        pub fn change_base_issuer(
+            &mut self,
+            base_id: u32,
+            new_issuer: AccountId,
+        ) -> Result<(), RmrkError> {
+            self.ensure_role(Role::Admin)?;
+            self.env()
+                .extension()
+                .change_base_issuer(base_id, new_issuer)?;
+            Ok(())
+        }
+
+        fn ensure_role(&self, role: Role) -> Result<(), RmrkError> {
+            if !self.has_role(role, self.env().caller()) {
+                return Err(RmrkError::MissingRole(role))
+            }
             Ok(())
         }
+
+        fn ensure_may_manage(&self, role: Role) -> Result<(), RmrkError> {
+            match role {
+                Role::Admin if self.env().caller() != self.owner => Err(RmrkError::NotOwner),
+                Role::Admin => Ok(()),
+                Role::Minter => self.ensure_role(Role::Admin),
+            }
+        }
+
+        /// Allows admins to manage a collection created through this contract.
+        ///
+        /// Having created the collection grants nothing once the admin role is revoked.
+        fn ensure_may_manage_collection(&self, collection_id: u32) -> Result<(), RmrkError> {
+            if !self.collections.contains(collection_id) {
+                return Err(RmrkError::UnknownCollection)
+            }
+            self.ensure_role(Role::Admin)
+        }
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use crate::{ResourceTypes, RmrkErrorCode, RMRK_PAGE_LIMIT};
+        use chain_extension_mock::MockRuntime;
+        use rmrk_chain_extension_types::{RmrkFunc, StatusCode};
 
         /// Imports `ink_lang` so we can use `#[ink::test]`.
         use ink_lang as ink;
 
         #[ink::test]
         fn create_collection_works() {
+            let runtime = MockRuntime::new();
+            let mut rmrk = Rmrk::new();
+            let metadata = "ipfs://ipfs/QmTG9ekqrdMh3dsehLYjC19fUSmPR31Ds2h6Jd7LnMZ9c7".to_string();
+            let symbol = "ROO".to_string();
+
+            let result = rmrk.create_collection(
+                metadata.clone().into_bytes(),
+                None,
+                symbol.clone().into_bytes(),
+            );
+            assert_eq!(result, Ok(0));
+            assert_eq!(ink_env::test::recorded_events().count(), 1);
+            assert_eq!(
+                rmrk.collection(0),
+                Ok(Some(CollectionInfo {
+                    issuer: runtime.origins().address,
+                    metadata: metadata.into_bytes(),
+                    max: None,
+                    symbol: symbol.into_bytes(),
+                    nfts_count: 0,
+                }))
+            );
+            assert_eq!(rmrk.create_collection(Vec::new(), None, Vec::new()), Ok(1));
+        }
+
+        #[ink::test]
+        fn roles_guard_collections_and_minting() {
+            let runtime = MockRuntime::new();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let set_caller = |account| runtime.set_caller(account);
+            let mut rmrk = Rmrk::new();
+            let create = |rmrk: &mut Rmrk| rmrk.create_collection(Vec::new(), None, Vec::new());
+
+            set_caller(accounts.bob);
+            assert_eq!(create(&mut rmrk), Err(RmrkError::MissingRole(Role::Admin)));
+            assert_eq!(rmrk.grant_role(Role::Admin, accounts.bob), Err(RmrkError::NotOwner));
+
+            set_caller(accounts.alice);
+            assert_eq!(rmrk.grant_role(Role::Admin, accounts.bob), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(create(&mut rmrk), Ok(0));
+            assert_eq!(rmrk.collection_owner(0), Some(accounts.bob));
+            assert_eq!(rmrk.grant_role(Role::Minter, accounts.charlie), Ok(()));
+            assert!(rmrk.has_role(Role::Minter, accounts.bob));
+
+            set_caller(accounts.charlie);
+            assert_eq!(create(&mut rmrk), Err(RmrkError::MissingRole(Role::Admin)));
+            assert_eq!(
+                rmrk.mint_ntf(accounts.django, 1, None, None, Vec::new(), true, None),
+                Err(RmrkError::UnknownCollection)
+            );
+            assert_eq!(
+                rmrk.mint_ntf(accounts.django, 0, None, None, Vec::new(), true, None),
+                Ok((0, 0))
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(rmrk.revoke_role(Role::Minter, accounts.charlie), Ok(()));
+            set_caller(accounts.charlie);
+            assert_eq!(
+                rmrk.mint_ntf(accounts.django, 0, None, None, Vec::new(), true, None),
+                Err(RmrkError::MissingRole(Role::Minter))
+            );
+        }
+
+        #[ink::test]
+        fn revoked_admin_loses_its_collections() {
+            let runtime = MockRuntime::new();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let contract = runtime.origins().address;
+            let mut rmrk = Rmrk::new();
+            assert_eq!(rmrk.grant_role(Role::Admin, accounts.bob), Ok(()));
+            runtime.set_caller(accounts.bob);
+            assert_eq!(rmrk.create_collection(Vec::new(), None, Vec::new()), Ok(0));
+
+            runtime.set_caller(accounts.alice);
+            assert_eq!(rmrk.revoke_role(Role::Admin, accounts.bob), Ok(()));
+            runtime.set_caller(accounts.bob);
+            assert_eq!(
+                rmrk.change_collection_issuer(0, accounts.bob),
+                Err(RmrkError::MissingRole(Role::Admin))
+            );
+            assert_eq!(
+                rmrk.remove_resource(0, 0, 0),
+                Err(RmrkError::MissingRole(Role::Admin))
+            );
+
+            // The contract owner still manages the collection.
+            runtime.set_caller(accounts.alice);
+            assert_eq!(rmrk.change_collection_issuer(0, contract), Ok(()));
+        }
+
+        #[ink::test]
+        fn mint_ntf_forwards_royalty() {
+            let runtime = MockRuntime::new();
+            let mut rmrk = Rmrk::new();
+            rmrk.create_collection(Vec::new(), None, Vec::new())
+                .expect("create_collection must work");
+            let mint = |rmrk: &mut Rmrk, royalty| {
+                rmrk.mint_ntf(
+                    AccountId::from([1; 32]),
+                    0,
+                    Some(AccountId::from([2; 32])),
+                    royalty,
+                    Vec::new(),
+                    true,
+                    None,
+                )
+            };
+
+            assert_eq!(mint(&mut rmrk, Some(Permill::from_percent(5))), Ok((0, 0)));
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            assert_eq!(
+                runtime.rmrk().nfts[&(0, 0)].royalty,
+                Some((AccountId::from([2; 32]), 50_000))
+            );
+            assert_eq!(
+                mint(&mut rmrk, Some(Permill(Permill::ACCURACY + 1))),
+                Err(RmrkError::RoyaltyOutOfRange)
+            );
+        }
+
+        #[ink::test]
+        fn mint_batch_reports_or_reverts() {
+            let runtime = MockRuntime::new();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut rmrk = Rmrk::new();
+            // Both collections are full after two NFTs.
+            for _ in 0..2 {
+                rmrk.create_collection(Vec::new(), Some(2), Vec::new())
+                    .expect("create_collection must work");
+            }
+            let items = vec![
+                (accounts.bob, b"ipfs://1".to_vec()),
+                (accounts.charlie, b"ipfs://2".to_vec()),
+                (accounts.django, b"ipfs://3".to_vec()),
+            ];
+            let full = RmrkErrorCode::CollectionFullOrLocked;
+
+            // The off-chain environment does not revert, on chain nothing would be minted.
+            assert_eq!(
+                rmrk.mint_batch(0, items.clone(), None, true, BatchMode::Atomic),
+                Err(RmrkError::BatchItemFailed { index: 2, error: full })
+            );
+            assert_eq!(
+                rmrk.mint_batch(1, items.clone(), None, true, BatchMode::PerItem),
+                Ok(vec![Ok((1, 0)), Ok((1, 1)), Err(RmrkError::ErrorCode(full))])
+            );
+            assert_eq!(runtime.rmrk().nfts[&(1, 1)].metadata, b"ipfs://2".to_vec());
+
+            let royalty = RoyaltyInfo {
+                recipient: accounts.alice,
+                amount: Permill(Permill::ACCURACY + 1),
+            };
+            assert_eq!(
+                rmrk.mint_batch(0, items.clone(), Some(royalty), true, BatchMode::PerItem),
+                Err(RmrkError::RoyaltyOutOfRange)
+            );
+            assert_eq!(rmrk.max_batch(), DEFAULT_MAX_BATCH);
+            assert_eq!(rmrk.set_max_batch(0), Err(RmrkError::InvalidMaxBatch));
+            assert_eq!(rmrk.set_max_batch(MAX_BATCH + 1), Err(RmrkError::InvalidMaxBatch));
+            assert_eq!(rmrk.set_max_batch(MAX_BATCH), Ok(()));
+            assert_eq!(rmrk.set_max_batch(2), Ok(()));
+            assert_eq!(
+                rmrk.mint_batch(0, items, None, true, BatchMode::PerItem),
+                Err(RmrkError::BatchTooLarge)
+            );
+            runtime.set_caller(accounts.bob);
+            assert_eq!(rmrk.set_max_batch(100), Err(RmrkError::NotOwner));
+        }
+
+        #[ink::test]
+        fn send_reports_invalid_destination() {
+            let runtime = MockRuntime::new();
+            let contract = runtime.origins().address;
+            let mut rmrk = Rmrk::new();
+            rmrk.create_collection(Vec::new(), None, Vec::new())
+                .expect("create_collection must work");
+            for _ in 0..2 {
+                rmrk.mint_ntf(contract, 0, None, None, Vec::new(), true, None)
+                    .expect("mint_ntf must work");
+            }
+
+            assert_eq!(
+                rmrk.send(0, 0, AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 1)),
+                Ok(())
+            );
+            assert_eq!(
+                rmrk.send(0, 1, AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0)),
+                Err(RmrkErrorCode::InvalidDestination.into())
+            );
+            assert_eq!(rmrk.children(0, 1, None, RMRK_PAGE_LIMIT), Ok(vec![(0, 0)]));
+        }
+
+        #[ink::test]
+        fn children_and_properties_are_paged() {
+            let runtime = MockRuntime::new();
+            let contract = runtime.origins().address;
+            let mut rmrk = Rmrk::new();
+            rmrk.create_collection(Vec::new(), None, Vec::new())
+                .expect("create_collection must work");
+            for _ in 0..4 {
+                rmrk.mint_ntf(contract, 0, None, None, Vec::new(), true, None)
+                    .expect("mint_ntf must work");
+            }
+            for nft_id in 1..4 {
+                rmrk.send(0, nft_id, AccountIdOrCollectionNftTuple::CollectionAndNftTuple(0, 0))
+                    .expect("send must work");
+            }
+
+            assert_eq!(rmrk.children(0, 0, None, 2), Ok(vec![(0, 1), (0, 2)]));
+            assert_eq!(rmrk.children(0, 0, Some((0, 2)), 2), Ok(vec![(0, 3)]));
+            // A limit above the page limit is lowered to it.
+            assert_eq!(rmrk.children(0, 0, None, u32::MAX).map(|page| page.len()), Ok(3));
+
+            for key in [b"a", b"b", b"c"] {
+                runtime
+                    .rmrk()
+                    .properties
+                    .insert((0, Some(0), key.to_vec()), b"value".to_vec());
+            }
+            let keys = |start: Option<&[u8]>, limit| {
+                rmrk.properties(0, Some(0), start.map(<[u8]>::to_vec), limit)
+                    .map(|page| page.into_iter().map(|property| property.key).collect::<Vec<_>>())
+            };
+            assert_eq!(keys(None, 2), Ok(vec![b"a".to_vec(), b"b".to_vec()]));
+            assert_eq!(keys(Some(b"b"), 2), Ok(vec![b"c".to_vec()]));
+            assert_eq!(keys(Some(b"c"), RMRK_PAGE_LIMIT), Ok(Vec::new()));
+        }
+
+        #[ink::test]
+        fn nft_queries_decode() {
+            let _runtime = MockRuntime::new();
+            let mut rmrk = Rmrk::new();
+            let recipient = AccountId::from([1; 32]);
+            rmrk.create_collection(Vec::new(), None, Vec::new())
+                .expect("create_collection must work");
+            rmrk.mint_ntf(recipient, 0, None, Some(Permill::from_percent(5)), b"ipfs://nft".to_vec(), true, None)
+                .expect("mint_ntf must work");
+
+            let nft = rmrk.nft(0, 0).expect("query must work").expect("NFT must exist");
+            assert_eq!(nft.royalty, Some(RoyaltyInfo { recipient, amount: Permill::from_percent(5) }));
+            assert_eq!(nft.metadata, b"ipfs://nft".to_vec());
+            assert_eq!(
+                rmrk.owner(0, 0),
+                Ok(Some(AccountIdOrCollectionNftTuple::AccountId(recipient)))
+            );
+            assert_eq!(rmrk.owner(0, 1), Ok(None));
+            assert_eq!(rmrk.next_nft_id(0), Ok(1));
+            assert_eq!(rmrk.collection_index(), Ok(1));
+        }
+
+        #[ink::test]
+        fn unmodelled_functions_fail() {
+            let _runtime = MockRuntime::new();
+            let mut rmrk = Rmrk::new();
+
+            assert_eq!(
+                rmrk.unequip((0, 1), (0, 0), 0, 1),
+                Err(RmrkErrorCode::Failed.into())
+            );
+        }
+
+        #[ink::test]
+        fn equip_reports_already_equipped() {
+            /// Reports the first slot as taken.
             struct MockedExtension;
             impl ink_env::test::ChainExtension for MockedExtension {
-                /// The static function id of the chain extension.
                 fn func_id(&self) -> u32 {
-                    3515
+                    RmrkFunc::Equip.into()
                 }
 
-                fn call(&mut self, _input: &[u8], output: &mut Vec<u8>) -> u32 {
-                    let ret: [u8; 32] = [1; 32];
-                    scale::Encode::encode_to(&ret, output);
-                    0
+                fn call(&mut self, input: &[u8], _output: &mut Vec<u8>) -> u32 {
+                    let input: Vec<u8> = scale::Decode::decode(&mut &input[..]).unwrap();
+                    let (_, _, _, _, slot_id): ((u32, u32), (u32, u32), u32, u32, u32) =
+                        scale::Decode::decode(&mut &input[..]).unwrap();
+                    if slot_id == 1 { StatusCode::AlreadyEquipped.into() } else { 0 }
                 }
             }
             ink_env::test::register_chain_extension(MockedExtension);
-            let rmrk = Rmrk::new();
-            let metadata = "ipfs://ipfs/QmTG9ekqrdMh3dsehLYjC19fUSmPR31Ds2h6Jd7LnMZ9c7".to_string();
-            let symbol = "ROO".to_string();
+            let mut rmrk = Rmrk::new();
+
+            assert_eq!(rmrk.equip((0, 1), (0, 0), 0, 0, 2), Ok(()));
+            assert_eq!(
+                rmrk.equip((0, 1), (0, 0), 0, 0, 1),
+                Err(RmrkErrorCode::AlreadyEquipped.into())
+            );
+        }
+
+        #[test]
+        fn permill_is_clamped_to_one_whole() {
+            assert_eq!(Permill::from_parts(Permill::ACCURACY + 1), Permill::from_percent(100));
+            assert_eq!(Permill::from_percent(u8::MAX).deconstruct(), Permill::ACCURACY);
+            assert_eq!(Permill::from_parts(50_000), Permill::from_percent(5));
+            assert!(!Permill(Permill::ACCURACY + 1).is_valid());
+        }
 
-            // Get contract address.
-            // let callee = ink_env::account_id::<ink_env::DefaultEnvironment>();
+        #[test]
+        fn resource_encoding_matches_rmrk_traits() {
+            let resource = ResourceTypes::Slot(SlotResource {
+                base: 1,
+                src: Some(b"a".to_vec()),
+                metadata: None,
+                slot: 2,
+                license: None,
+                thumb: None,
+            });
 
-            let _result = rmrk.create_collection(
-                metadata.into_bytes(),
-                None,
-                symbol.clone().into_bytes(),
+            // Variant index, base, `Some` src, `None` metadata, slot, `None` license and thumb.
+            assert_eq!(
+                scale::Encode::encode(&resource),
+                [&[2, 1, 0, 0, 0, 1, 1 << 2, b'a', 0, 2, 0, 0, 0, 0, 0][..]].concat()
             );
         }
     }
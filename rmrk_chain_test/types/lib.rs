@@ -0,0 +1,302 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Function IDs and status codes shared by `rmrk_chain_test` and `LocalChainExtension`.
+
+/// First ID of the RMRK range, `RmrkFunc` IDs follow it.
+pub const RMRK_EXTENSION: u32 = 3500;
+
+/// Most entries `Children` and `Properties` return in one page, a larger `limit` is lowered
+/// to it. The runtime charges the reads of a full page.
+pub const RMRK_PAGE_LIMIT: u32 = 25;
+
+/// Most NFTs `BalanceOf` counts in one page, a larger balance is counted over several
+/// pages. The runtime charges the reads of a full page.
+pub const RMRK_BALANCE_LIMIT: u32 = 100;
+
+/// Functions of `pallet_rmrk_core` and `pallet_rmrk_equip` exposed through the chain extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RmrkFunc {
+    /// `next_nft_id(collection_id)`
+    NextNftId = 3501,
+    /// `collection_index()`
+    CollectionIndex = 3502,
+    /// `collections(collection_id)`
+    CollectionInfo = 3503,
+    /// `nfts(collection_id, nft_id)`
+    NftInfo = 3504,
+    /// `children((collection_id, nft_id))`, a page of up to `limit` keys after `start`
+    /// in storage order
+    Children = 3506,
+    /// `properties((collection_id, nft_id))`, a page of up to `limit` keys and values
+    /// after the key `start` in storage order
+    Properties = 3510,
+    /// Number of NFTs of a collection owned directly by an account, a page of up to
+    /// `RMRK_BALANCE_LIMIT` after the NFT ID `start` in storage order. Returns
+    /// `(count, last)`, `last` is the ID to start the next page after, `None` when the
+    /// page is not full
+    BalanceOf = 3511,
+    /// `mint_nft(owner, collection_id, royalty_recipient, royalty, metadata, transferable, resources)`,
+    /// returns `(collection_id, nft_id)`
+    MintNft = 3513,
+    /// `create_collection(metadata, max, symbol)`, returns `collection_id`
+    CreateCollection = 3515,
+    /// `burn_nft(collection_id, nft_id)`
+    BurnNft = 3516,
+    /// `send(collection_id, nft_id, new_owner)`
+    Send = 3518,
+    /// `accept_nft(collection_id, nft_id, new_owner)`
+    AcceptNft = 3519,
+    /// `reject_nft(collection_id, nft_id)`
+    RejectNft = 3520,
+    /// `change_collection_issuer(collection_id, new_issuer)`
+    ChangeCollectionIssuer = 3521,
+    /// `add_basic_resource(collection_id, nft_id, resource)`
+    AddBasicResource = 3524,
+    /// `add_composable_resource(collection_id, nft_id, resource)`
+    AddComposableResource = 3525,
+    /// `add_slot_resource(collection_id, nft_id, resource)`
+    AddSlotResource = 3526,
+    /// `accept_resource(collection_id, nft_id, resource_id)`
+    AcceptResource = 3527,
+    /// `remove_resource(collection_id, nft_id, resource_id)`
+    RemoveResource = 3528,
+    /// `accept_resource_removal(collection_id, nft_id, resource_id)`
+    AcceptResourceRemoval = 3529,
+    /// `set_priority(collection_id, nft_id, priorities)`
+    SetPriority = 3530,
+    /// `pallet_rmrk_equip::create_base(base_type, symbol, parts)`
+    CreateBase = 3531,
+    /// `pallet_rmrk_equip::theme_add(base_id, theme)`
+    ThemeAdd = 3532,
+    /// `pallet_rmrk_equip::equip(item, equipper, resource_id, base_id, slot_id)`
+    Equip = 3533,
+    /// `pallet_rmrk_equip::unequip(item, unequipper, base_id, slot_id)`
+    Unequip = 3534,
+    /// `pallet_rmrk_equip::equippable(base_id, slot_id, equippables)`
+    Equippable = 3535,
+    /// `pallet_rmrk_equip::change_base_issuer(base_id, new_issuer)`
+    ChangeBaseIssuer = 3536,
+    /// `send(collection_id, nft_id, new_owner)`, signed by the caller of the contract
+    ///
+    /// Every other function is signed by the contract itself. This one lets a
+    /// contract move NFTs of the accounts calling it, so the runtime only allows
+    /// it to contracts governance put in `CallerSigningContracts`.
+    SendFromCaller = 3537,
+}
+
+impl RmrkFunc {
+    /// Every declared function, in ID order.
+    pub const ALL: &'static [RmrkFunc] = &[
+        RmrkFunc::NextNftId,
+        RmrkFunc::CollectionIndex,
+        RmrkFunc::CollectionInfo,
+        RmrkFunc::NftInfo,
+        RmrkFunc::Children,
+        RmrkFunc::Properties,
+        RmrkFunc::BalanceOf,
+        RmrkFunc::MintNft,
+        RmrkFunc::CreateCollection,
+        RmrkFunc::BurnNft,
+        RmrkFunc::Send,
+        RmrkFunc::AcceptNft,
+        RmrkFunc::RejectNft,
+        RmrkFunc::ChangeCollectionIssuer,
+        RmrkFunc::AddBasicResource,
+        RmrkFunc::AddComposableResource,
+        RmrkFunc::AddSlotResource,
+        RmrkFunc::AcceptResource,
+        RmrkFunc::RemoveResource,
+        RmrkFunc::AcceptResourceRemoval,
+        RmrkFunc::SetPriority,
+        RmrkFunc::CreateBase,
+        RmrkFunc::ThemeAdd,
+        RmrkFunc::Equip,
+        RmrkFunc::Unequip,
+        RmrkFunc::Equippable,
+        RmrkFunc::ChangeBaseIssuer,
+        RmrkFunc::SendFromCaller,
+    ];
+}
+
+impl From<RmrkFunc> for u32 {
+    fn from(func: RmrkFunc) -> Self {
+        func as u32
+    }
+}
+
+impl TryFrom<u32> for RmrkFunc {
+    /// The unknown `func_id`.
+    type Error = u32;
+
+    fn try_from(func_id: u32) -> Result<Self, Self::Error> {
+        RmrkFunc::ALL
+            .iter()
+            .copied()
+            .find(|func| *func as u32 == func_id)
+            .ok_or(func_id)
+    }
+}
+
+/// Status codes returned by `LocalChainExtension` for the RMRK functions.
+///
+/// A non-zero code reports an expected failure and leaves the output buffer empty.
+///
+/// | code | meaning                                                        |
+/// |------|----------------------------------------------------------------|
+/// | 0    | success, the output buffer holds the SCALE encoded result      |
+/// | 1    | the pallet call failed for a reason without a dedicated code   |
+/// | 2    | the collection does not exist                                  |
+/// | 3    | the contract is not allowed to act on the collection or NFT    |
+/// | 4    | the collection is full or locked                               |
+/// | 5    | the NFT is not transferable                                    |
+/// | 6    | metadata, symbol or another bounded argument is too long       |
+/// | 7    | the NFT does not exist                                         |
+/// | 8    | the NFT is locked, e.g. while equipped                         |
+/// | 9    | the NFT cannot be sent to itself or one of its descendants     |
+/// | 10   | the resource does not exist on the NFT                         |
+/// | 11   | the resource is not pending acceptance or removal              |
+/// | 12   | the base does not exist                                        |
+/// | 13   | the part does not exist in the base                            |
+/// | 14   | the item cannot be equipped into that slot                     |
+/// | 15   | the slot is already equipped                                   |
+/// | 16   | the item is not equipped                                       |
+/// | 17   | the base needs a `default` theme before other themes           |
+/// | 18   | the royalty is more than one whole                             |
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum StatusCode {
+    Success = 0,
+    Failed = 1,
+    CollectionUnknown = 2,
+    NoPermission = 3,
+    CollectionFullOrLocked = 4,
+    NonTransferable = 5,
+    TooLong = 6,
+    NftUnknown = 7,
+    NftLocked = 8,
+    InvalidDestination = 9,
+    ResourceUnknown = 10,
+    ResourceNotPending = 11,
+    BaseUnknown = 12,
+    PartUnknown = 13,
+    NotEquippable = 14,
+    AlreadyEquipped = 15,
+    NotEquipped = 16,
+    NeedsDefaultTheme = 17,
+    RoyaltyOutOfRange = 18,
+}
+
+impl StatusCode {
+    /// Every declared status code, in code order.
+    pub const ALL: &'static [StatusCode] = &[
+        StatusCode::Success,
+        StatusCode::Failed,
+        StatusCode::CollectionUnknown,
+        StatusCode::NoPermission,
+        StatusCode::CollectionFullOrLocked,
+        StatusCode::NonTransferable,
+        StatusCode::TooLong,
+        StatusCode::NftUnknown,
+        StatusCode::NftLocked,
+        StatusCode::InvalidDestination,
+        StatusCode::ResourceUnknown,
+        StatusCode::ResourceNotPending,
+        StatusCode::BaseUnknown,
+        StatusCode::PartUnknown,
+        StatusCode::NotEquippable,
+        StatusCode::AlreadyEquipped,
+        StatusCode::NotEquipped,
+        StatusCode::NeedsDefaultTheme,
+        StatusCode::RoyaltyOutOfRange,
+    ];
+}
+
+impl From<StatusCode> for u32 {
+    fn from(status: StatusCode) -> Self {
+        status as u32
+    }
+}
+
+impl TryFrom<u32> for StatusCode {
+    /// The unknown status code.
+    type Error = u32;
+
+    fn try_from(status_code: u32) -> Result<Self, Self::Error> {
+        StatusCode::ALL
+            .iter()
+            .copied()
+            .find(|status| *status as u32 == status_code)
+            .ok_or(status_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn func_ids_round_trip() {
+        for func in RmrkFunc::ALL {
+            assert!(u32::from(*func) > RMRK_EXTENSION);
+            assert_eq!(RmrkFunc::try_from(u32::from(*func)), Ok(*func));
+        }
+        assert_eq!(RmrkFunc::try_from(RMRK_EXTENSION), Err(RMRK_EXTENSION));
+    }
+
+    /// The function declared after `func`. The match is exhaustive, so a new variant
+    /// does not build until it is linked in here.
+    fn declared_after(func: RmrkFunc) -> Option<RmrkFunc> {
+        use RmrkFunc::*;
+        match func {
+            NextNftId => Some(CollectionIndex),
+            CollectionIndex => Some(CollectionInfo),
+            CollectionInfo => Some(NftInfo),
+            NftInfo => Some(Children),
+            Children => Some(Properties),
+            Properties => Some(BalanceOf),
+            BalanceOf => Some(MintNft),
+            MintNft => Some(CreateCollection),
+            CreateCollection => Some(BurnNft),
+            BurnNft => Some(Send),
+            Send => Some(AcceptNft),
+            AcceptNft => Some(RejectNft),
+            RejectNft => Some(ChangeCollectionIssuer),
+            ChangeCollectionIssuer => Some(AddBasicResource),
+            AddBasicResource => Some(AddComposableResource),
+            AddComposableResource => Some(AddSlotResource),
+            AddSlotResource => Some(AcceptResource),
+            AcceptResource => Some(RemoveResource),
+            RemoveResource => Some(AcceptResourceRemoval),
+            AcceptResourceRemoval => Some(SetPriority),
+            SetPriority => Some(CreateBase),
+            CreateBase => Some(ThemeAdd),
+            ThemeAdd => Some(Equip),
+            Equip => Some(Unequip),
+            Unequip => Some(Equippable),
+            Equippable => Some(ChangeBaseIssuer),
+            ChangeBaseIssuer => Some(SendFromCaller),
+            SendFromCaller => None,
+        }
+    }
+
+    #[test]
+    fn all_lists_every_func_once_in_id_order() {
+        let declared: Vec<RmrkFunc> =
+            core::iter::successors(Some(RmrkFunc::NextNftId), |func| declared_after(*func))
+                .collect();
+        assert_eq!(RmrkFunc::ALL, &declared[..]);
+        // Strictly increasing IDs also rule out a function listed twice.
+        assert!(RmrkFunc::ALL
+            .windows(2)
+            .all(|pair| u32::from(pair[0]) < u32::from(pair[1])));
+    }
+
+    #[test]
+    fn status_codes_round_trip() {
+        for status in StatusCode::ALL {
+            assert_eq!(StatusCode::try_from(u32::from(*status)), Ok(*status));
+        }
+        assert_eq!(StatusCode::try_from(99), Err(99));
+    }
+}